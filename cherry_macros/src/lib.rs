@@ -1,17 +1,18 @@
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{
     self, parse_macro_input, Data, DataStruct, DeriveInput, Field, Fields, GenericArgument, Meta,
     Path, PathArguments, Type,
 };
 
+const INTO_ATTRIBUTE: &str = "into";
 const OMIT_ATTRIBUTE: &str = "omit";
 
-#[proc_macro_derive(Builder, attributes(omit))]
+#[proc_macro_derive(Builder, attributes(into, omit))]
 pub fn derive_builder(token_stream: TokenStream) -> TokenStream {
     let input = parse_macro_input!(token_stream as DeriveInput);
     let generics = input.generics;
-    let where_clause = &generics.where_clause;
+    let (impl_generics, type_generics, where_clause) = generics.split_for_impl();
     let name = input.ident;
 
     let fields = match input.data {
@@ -30,28 +31,34 @@ pub fn derive_builder(token_stream: TokenStream) -> TokenStream {
         }
 
         let name = &field.ident.as_ref().unwrap();
+        let setter = format_ident!("set_{}", name);
         let ty = &field.ty;
 
-        let function = match option_inner_type(ty) {
-            Some(option_type) => quote! {
-                pub fn #name(mut self, value: #option_type) -> Self {
-                    self.#name = Some(value);
-                    self
-                }
-            },
-            None => quote! {
-                pub fn #name(mut self, value: #ty) -> Self {
-                    self.#name = value;
-                    self
-                }
-            },
+        let (parameter, value) = match (option_inner_type(ty), should_convert(&field)) {
+            (Some(option_type), false) => (quote!(#option_type), quote!(Some(value))),
+            (Some(option_type), true) => {
+                (quote!(impl Into<#option_type>), quote!(Some(value.into())))
+            }
+            (None, false) => (quote!(#ty), quote!(value)),
+            (None, true) => (quote!(impl Into<#ty>), quote!(value.into())),
         };
 
-        functions.extend(function);
+        // a builder function, along with a setter for updating the field in
+        // place
+        functions.extend(quote! {
+            pub fn #name(mut self, value: #parameter) -> Self {
+                self.#name = #value;
+                self
+            }
+
+            pub fn #setter(&mut self, value: #parameter) {
+                self.#name = #value;
+            }
+        });
     }
 
     quote! {
-        impl #generics #name #generics #where_clause {
+        impl #impl_generics #name #type_generics #where_clause {
             #functions
         }
     }
@@ -62,6 +69,10 @@ fn should_omit(field: &Field) -> bool {
     has_attribute(OMIT_ATTRIBUTE, field)
 }
 
+fn should_convert(field: &Field) -> bool {
+    has_attribute(INTO_ATTRIBUTE, field)
+}
+
 fn has_attribute(name: &str, field: &Field) -> bool {
     field
         .attrs
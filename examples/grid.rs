@@ -0,0 +1,55 @@
+use cherry::widget::{
+    container::{Alignment, Container},
+    grid::{Cell, Grid, Track},
+    text::Text,
+    Widget,
+};
+use embedded_graphics::{
+    mono_font::{ascii::FONT_10X20, MonoTextStyle},
+    pixelcolor::Rgb888,
+    prelude::{Point, RgbColor, Size},
+};
+use embedded_graphics_simulator::{OutputSettingsBuilder, SimulatorDisplay, Window};
+use std::convert::Infallible;
+
+fn main() -> Result<(), Infallible> {
+    let display_size = Size::new(300, 300);
+    let mut display: SimulatorDisplay<Rgb888> = SimulatorDisplay::new(display_size);
+
+    let character_style = MonoTextStyle::new(&FONT_10X20, Rgb888::BLACK);
+    let key = |row, column, label| {
+        Cell::new(
+            row,
+            column,
+            Container::new()
+                .alignment(Alignment::Center)
+                .background_color(Rgb888::WHITE)
+                .children(vec![Text::new(label, character_style).boxed()])
+                .boxed(),
+        )
+    };
+
+    let grid = Grid::new()
+        .columns(vec![Track::Fraction(1); 3])
+        .rows(vec![Track::Fraction(1); 4])
+        .gap(4)
+        .cells(vec![
+            key(0, 0, "1"),
+            key(0, 1, "2"),
+            key(0, 2, "3"),
+            key(1, 0, "4"),
+            key(1, 1, "5"),
+            key(1, 2, "6"),
+            key(2, 0, "7"),
+            key(2, 1, "8"),
+            key(2, 2, "9"),
+            key(3, 0, "0").column_span(2),
+            key(3, 2, "OK"),
+        ]);
+    grid.draw(&mut display, Point::zero(), display_size)?;
+
+    let output_settings = OutputSettingsBuilder::new().build();
+    Window::new("Grid", &output_settings).show_static(&display);
+
+    Ok(())
+}
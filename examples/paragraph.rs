@@ -0,0 +1,50 @@
+use cherry::widget::{
+    container::{Border, Container, Insets},
+    paragraph::{Paragraph, TextAlignment},
+    Widget,
+};
+use embedded_graphics::{
+    mono_font::{ascii::FONT_6X10, MonoTextStyle},
+    pixelcolor::Rgb888,
+    prelude::{Point, RgbColor, Size},
+};
+use embedded_graphics_simulator::{OutputSettingsBuilder, SimulatorDisplay, Window};
+use std::convert::Infallible;
+
+const MESSAGE: &str = "The firmware update finished successfully. The device will restart \
+    in a few seconds, and any unsaved settings will be lost.\nPress the button to restart now.";
+
+fn main() -> Result<(), Infallible> {
+    let display_size = Size::new(200, 200);
+    let mut display: SimulatorDisplay<Rgb888> = SimulatorDisplay::new(display_size);
+
+    let character_style = MonoTextStyle::new(&FONT_6X10, Rgb888::WHITE);
+    let notification = |alignment| {
+        Container::new()
+            .border(Border {
+                color: Rgb888::CYAN,
+                width: 1,
+            })
+            .padding(Insets::all(4))
+            .children(vec![Paragraph::new(MESSAGE, character_style)
+                .alignment(alignment)
+                .line_spacing(2)
+                .max_lines(4)
+                .boxed()])
+            .boxed()
+    };
+
+    let widget = Container::new()
+        .gap(8)
+        .padding(Insets::all(8))
+        .children(vec![
+            notification(TextAlignment::Left),
+            notification(TextAlignment::Justified),
+        ]);
+    widget.draw(&mut display, Point::zero(), display_size)?;
+
+    let output_settings = OutputSettingsBuilder::new().build();
+    Window::new("Paragraph", &output_settings).show_static(&display);
+
+    Ok(())
+}
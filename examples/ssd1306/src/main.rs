@@ -8,7 +8,7 @@ use alloc::vec;
 use alloc_cortex_m::CortexMHeap;
 use cherry::widget::{
     container::{Alignment, Axis, Border, Container, Insets, Justification},
-    Widget,
+    Key, LayoutOptions, Tree, Widget,
 };
 use defmt_rtt as _;
 use embedded_graphics::{pixelcolor::BinaryColor, prelude::*, primitives::CornerRadii};
@@ -102,16 +102,13 @@ fn configure_display() -> Screen {
 }
 
 fn animate(display: &mut Screen) -> ! {
-    let size = display.size();
     let mut ascending = true;
     let mut block_size: u32 = MIN_BLOCK_SIZE;
+    let mut tree = Tree::new(widget(block_size).boxed(), display.bounding_box());
 
     loop {
-        display.clear();
-
-        let widget = widget(block_size);
-        widget.draw(display, Point::zero(), size).unwrap();
-
+        // only the blocks that changed size are redrawn
+        tree.draw(display, BinaryColor::Off).unwrap();
         display.flush().unwrap();
 
         if ascending && block_size == MAX_BLOCK_SIZE {
@@ -125,6 +122,14 @@ fn animate(display: &mut Screen) -> ! {
         } else {
             block_size -= BLOCK_SIZE_INCREMENT;
         }
+
+        let sizes = [block_size, MAX_BLOCK_SIZE - block_size, block_size];
+        for (key, size) in (0..).zip(sizes) {
+            tree.update(Key(key), |block: &mut Container<Screen>| {
+                block.set_width(size);
+                block.set_height(size);
+            });
+        }
     }
 }
 
@@ -140,9 +145,9 @@ where
             width: 1,
         })
         .children(vec![
-            block(block_size).boxed(),
-            block(MAX_BLOCK_SIZE - block_size).boxed(),
-            block(block_size).boxed(),
+            block(block_size, 0).boxed(),
+            block(MAX_BLOCK_SIZE - block_size, 1).boxed(),
+            block(block_size, 2).boxed(),
         ])
         .corner_radii(CornerRadii::new(Size::new(10, 10)))
         .justification(Justification::SpaceBetween)
@@ -150,12 +155,13 @@ where
         .width(200)
 }
 
-fn block<Display>(size: u32) -> Container<Display>
+fn block<Display>(size: u32, key: u32) -> Container<Display>
 where
     Display: DrawTarget<Color = BinaryColor>,
 {
     Container::new()
         .background_color(BinaryColor::On)
+        .layout_options(LayoutOptions::new().key(key))
         .width(size)
         .height(size)
 }
@@ -8,6 +8,8 @@
 //! ```shell
 //! cargo run --example container
 //! cargo run --example text
+//! cargo run --example grid
+//! cargo run --example paragraph
 //! ```
 //!
 //! [examples]: https://github.com/peterstuart/cherry/tree/main/examples
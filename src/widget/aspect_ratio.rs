@@ -0,0 +1,48 @@
+use super::container::Axis;
+
+/// The ratio of a widget's width to its height.
+#[derive(Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub struct AspectRatio {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl AspectRatio {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self { width, height }
+    }
+
+    pub fn square() -> Self {
+        Self::new(1, 1)
+    }
+
+    /// The dimension along `axis` that keeps the ratio, given the dimension
+    /// along the opposite axis.
+    pub fn dimension_for_axis(&self, other_dimension: u32, axis: Axis) -> u32 {
+        let (numerator, denominator) = match axis {
+            Axis::Horizontal => (self.width, self.height),
+            Axis::Vertical => (self.height, self.width),
+        };
+
+        (other_dimension as u64 * numerator as u64)
+            .checked_div(denominator as u64)
+            .map_or(0, |dimension| dimension.min(u32::MAX as u64) as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dimension_for_axis() {
+        let ratio = AspectRatio::new(16, 9);
+        assert_eq!(ratio.dimension_for_axis(90, Axis::Horizontal), 160);
+        assert_eq!(ratio.dimension_for_axis(160, Axis::Vertical), 90);
+        assert_eq!(
+            AspectRatio::new(1, 0).dimension_for_axis(10, Axis::Horizontal),
+            0
+        );
+    }
+}
@@ -17,8 +17,8 @@ impl AxisSize<u32> for Size {
 
     fn add_to_axis(&mut self, value: u32, axis: Axis) {
         match axis {
-            Axis::Horizontal => self.width += value,
-            Axis::Vertical => self.height += value,
+            Axis::Horizontal => self.width = self.width.saturating_add(value),
+            Axis::Vertical => self.height = self.height.saturating_add(value),
         }
     }
 }
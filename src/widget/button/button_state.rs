@@ -0,0 +1,10 @@
+/// The state of a `Button`, which decides its style.
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub enum ButtonState {
+    #[default]
+    Normal,
+    Focused,
+    Pressed,
+    Disabled,
+}
@@ -0,0 +1,364 @@
+mod button_state;
+
+pub use button_state::ButtonState;
+
+use super::{
+    container::{Alignment, BoxStyle, Container, Insets, Justification},
+    Event, IntrinsicSize, KeyCode, LayoutNode, LayoutOptions, Length, Measurement, Message, Widget,
+};
+use alloc::{boxed::Box, vec, vec::Vec};
+use core::{
+    any::Any,
+    hash::{Hash, Hasher},
+};
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+/// A button, which centers its child (such as a `Text` or an `Image`) in a
+/// box that is styled according to the button's state. It sends its message
+/// when it is tapped, or selected while it has focus.
+///
+/// Fields that are `None` in the focused, pressed and disabled styles are
+/// taken from the normal style. The state doesn't change the layout, so the
+/// border should have the same width in every style.
+pub struct Button<Display, M>
+where
+    Display: DrawTarget,
+{
+    container: Container<Display>,
+    disabled: bool,
+    disabled_style: BoxStyle<Display::Color>,
+    focused: bool,
+    focused_style: BoxStyle<Display::Color>,
+    message: M,
+    normal_style: BoxStyle<Display::Color>,
+    pressed: bool,
+    pressed_style: BoxStyle<Display::Color>,
+}
+
+impl<Display, M> Button<Display, M>
+where
+    Display: DrawTarget,
+{
+    pub fn new(child: Box<dyn Widget<Display>>, message: M) -> Self {
+        Self {
+            container: Container::new()
+                .alignment(Alignment::Center)
+                .justification(Justification::Center)
+                .children(vec![child]),
+            disabled: false,
+            disabled_style: BoxStyle::new(),
+            focused: false,
+            focused_style: BoxStyle::new(),
+            message,
+            normal_style: BoxStyle::new(),
+            pressed: false,
+            pressed_style: BoxStyle::new(),
+        }
+    }
+
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.set_disabled(disabled);
+        self
+    }
+
+    pub fn set_disabled(&mut self, disabled: bool) {
+        self.disabled = disabled;
+        self.pressed = false;
+        self.apply_style();
+    }
+
+    /// Sets the style for `state`.
+    pub fn style(mut self, state: ButtonState, style: BoxStyle<Display::Color>) -> Self {
+        self.set_style(state, style);
+        self
+    }
+
+    pub fn set_style(&mut self, state: ButtonState, style: BoxStyle<Display::Color>) {
+        match state {
+            ButtonState::Normal => self.normal_style = style,
+            ButtonState::Focused => self.focused_style = style,
+            ButtonState::Pressed => self.pressed_style = style,
+            ButtonState::Disabled => self.disabled_style = style,
+        }
+
+        self.apply_style();
+    }
+
+    pub fn layout_options(mut self, layout_options: LayoutOptions) -> Self {
+        self.container = self.container.layout_options(layout_options);
+        self
+    }
+
+    /// The space between the box and the child.
+    pub fn padding(mut self, padding: impl Into<Insets<Length>>) -> Self {
+        self.container = self.container.padding(padding);
+        self
+    }
+
+    /// The state, where being disabled takes precedence over being pressed,
+    /// which takes precedence over having focus.
+    pub fn state(&self) -> ButtonState {
+        if self.disabled {
+            ButtonState::Disabled
+        } else if self.pressed {
+            ButtonState::Pressed
+        } else if self.focused {
+            ButtonState::Focused
+        } else {
+            ButtonState::Normal
+        }
+    }
+
+    fn set_pressed(&mut self, pressed: bool) {
+        self.pressed = pressed;
+        self.apply_style();
+    }
+
+    fn apply_style(&mut self) {
+        let style = match self.state() {
+            ButtonState::Normal => self.normal_style,
+            ButtonState::Focused => self.focused_style.or(self.normal_style),
+            ButtonState::Pressed => self.pressed_style.or(self.normal_style),
+            ButtonState::Disabled => self.disabled_style.or(self.normal_style),
+        };
+
+        self.container.set_box_style(style);
+    }
+}
+
+impl<Display, M> Widget<Display> for Button<Display, M>
+where
+    Display: DrawTarget,
+    M: 'static + Clone,
+{
+    fn intrinsic_size(&self) -> IntrinsicSize {
+        self.container.intrinsic_size()
+    }
+
+    fn layout_options(&self) -> LayoutOptions {
+        Widget::layout_options(&self.container)
+    }
+
+    fn height_for_width(&self, measurement: &Measurement, width: u32) -> Option<u32> {
+        self.container.height_for_width(measurement, width)
+    }
+
+    fn measure(&self) -> Measurement {
+        self.container.measure()
+    }
+
+    fn measure_with_children(&self, children: Vec<Measurement>) -> Measurement {
+        self.container.measure_with_children(children)
+    }
+
+    fn arrange(&self, measurement: &Measurement, bounds: Rectangle) -> LayoutNode {
+        self.container
+            .arrange(measurement, bounds)
+            .with_fingerprint(self.fingerprint())
+    }
+
+    fn hash_appearance(&self, mut state: &mut dyn Hasher) {
+        self.state().hash(&mut state);
+    }
+
+    fn focusable(&self) -> bool {
+        !self.disabled
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+        self.apply_style();
+    }
+
+    fn focused(&self) -> bool {
+        self.focused
+    }
+
+    /// A touch that moves off the button stops pressing it, and a touch that
+    /// ends on the button (after starting on it) taps it.
+    fn event(&mut self, event: &Event, layout: &LayoutNode) -> Option<Message> {
+        if self.disabled {
+            return None;
+        }
+
+        match *event {
+            Event::TouchDown(point) => {
+                self.set_pressed(layout.bounds.contains(point));
+                None
+            }
+            Event::TouchMove(point) => {
+                if self.pressed && !layout.bounds.contains(point) {
+                    self.set_pressed(false);
+                }
+                None
+            }
+            Event::TouchUp(point) => {
+                let tapped = self.pressed && layout.bounds.contains(point);
+                self.set_pressed(false);
+                tapped.then(|| Box::new(self.message.clone()) as Message)
+            }
+            Event::Key(KeyCode::Select) if self.focused => Some(Box::new(self.message.clone())),
+            Event::Key(_) | Event::Encoder(_) => None,
+        }
+    }
+
+    fn child_mut(&mut self, index: usize) -> Option<&mut (dyn Widget<Display> + 'static)> {
+        self.container.child_mut(index)
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn draw_clipped(
+        &self,
+        display: &mut Display,
+        layout: &LayoutNode,
+        clip: Rectangle,
+    ) -> Result<(), Display::Error> {
+        self.container.draw_clipped(display, layout, clip)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::{container::Axis, Key, Tree};
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    enum Message {
+        Save,
+    }
+
+    fn button() -> Button<MockDisplay<BinaryColor>, Message> {
+        let icon = Container::new().width(1).height(1).boxed();
+        Button::new(icon, Message::Save)
+            .padding(Insets::all(1))
+            .style(
+                ButtonState::Pressed,
+                BoxStyle::new().background_color(BinaryColor::On),
+            )
+    }
+
+    fn message(message: Option<crate::widget::Message>) -> Option<Message> {
+        message.and_then(|message| message.downcast_ref().copied())
+    }
+
+    #[test]
+    fn touch() {
+        let mut button = button();
+        let layout = Widget::<MockDisplay<BinaryColor>>::layout(
+            &button,
+            Rectangle::new(Point::zero(), Size::new(3, 3)),
+        );
+        let (inside, outside) = (Point::new(1, 1), Point::new(5, 5));
+
+        button.event(&Event::TouchDown(inside), &layout);
+        assert_eq!(button.state(), ButtonState::Pressed);
+        assert_eq!(
+            message(button.event(&Event::TouchUp(inside), &layout)),
+            Some(Message::Save)
+        );
+        assert_eq!(button.state(), ButtonState::Normal);
+
+        // moving off the button cancels the tap
+        button.event(&Event::TouchDown(inside), &layout);
+        button.event(&Event::TouchMove(outside), &layout);
+        assert_eq!(button.state(), ButtonState::Normal);
+        assert_eq!(
+            message(button.event(&Event::TouchUp(inside), &layout)),
+            None
+        );
+
+        button.set_disabled(true);
+        button.event(&Event::TouchDown(inside), &layout);
+        assert_eq!(button.state(), ButtonState::Disabled);
+        assert_eq!(
+            message(button.event(&Event::TouchUp(inside), &layout)),
+            None
+        );
+    }
+
+    #[test]
+    fn draw() {
+        let mut button = button();
+        let layout = Widget::<MockDisplay<BinaryColor>>::layout(
+            &button,
+            Rectangle::new(Point::zero(), Size::new(3, 3)),
+        );
+
+        let mut display = MockDisplay::new();
+        button.draw_layout(&mut display, &layout).unwrap();
+        display.assert_pattern(&[]);
+
+        button.event(&Event::TouchDown(Point::new(1, 1)), &layout);
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        button.draw_layout(&mut display, &layout).unwrap();
+        display.assert_pattern(&["###", "###", "###"]);
+    }
+
+    #[test]
+    fn select() {
+        let mut tree = Tree::new(
+            button().boxed(),
+            Rectangle::new(Point::zero(), Size::new(3, 3)),
+        );
+
+        tree.event(&Event::Encoder(1));
+        assert_eq!(tree.focus(), Some(0));
+        assert_eq!(
+            message(tree.event(&Event::Key(KeyCode::Select))),
+            Some(Message::Save)
+        );
+
+        // a button without focus ignores select, even when the event is sent
+        // to it directly
+        let mut button = button();
+        let layout = Widget::<MockDisplay<BinaryColor>>::layout(
+            &button,
+            Rectangle::new(Point::zero(), Size::new(3, 3)),
+        );
+        assert_eq!(
+            message(button.event(&Event::Key(KeyCode::Select), &layout)),
+            None
+        );
+    }
+
+    #[test]
+    fn disable_focused() {
+        let keyed = |key| {
+            button()
+                .layout_options(LayoutOptions::new().key(key))
+                .boxed()
+        };
+        let mut tree = Tree::new(
+            Container::new()
+                .axis(Axis::Horizontal)
+                .children(vec![keyed(1), keyed(2), keyed(3)])
+                .boxed(),
+            Rectangle::new(Point::zero(), Size::new(9, 3)),
+        );
+        let disable = |tree: &mut Tree<_>, key| {
+            tree.update(Key(key), |button: &mut Button<_, Message>| {
+                button.set_disabled(true)
+            })
+        };
+
+        // the focus moves back when a button before the focused one is disabled
+        tree.set_focus(Some(2));
+        assert!(disable(&mut tree, 1));
+        assert_eq!(tree.focus(), Some(1));
+
+        // and is cleared when the focused button is disabled
+        assert!(disable(&mut tree, 3));
+        assert_eq!(tree.focus(), None);
+        tree.update(Key(3), |button: &mut Button<_, Message>| {
+            assert!(!button.focused)
+        });
+    }
+}
@@ -1,13 +1,38 @@
-#[derive(Clone, Copy, Eq, PartialEq)]
+use crate::widget::LayoutOptions;
+
+use super::Axis;
+
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
 pub enum Alignment {
+    #[default]
     Stretch,
     Start,
     Center,
     End,
 }
 
-impl Default for Alignment {
-    fn default() -> Self {
-        Self::Stretch
+impl Alignment {
+    /// Returns the offset and size of a child placed in `available` space
+    /// along `axis`, where `Stretch` fills all of the space.
+    pub(crate) fn fill(
+        &self,
+        available: u32,
+        intrinsic_size: Option<u32>,
+        layout_options: &LayoutOptions,
+        axis: Axis,
+    ) -> (u32, u32) {
+        let size = match self {
+            Self::Stretch => available,
+            _ => intrinsic_size.unwrap_or(0),
+        };
+        let size = layout_options.clamp_for_axis(size, axis).min(available);
+
+        let offset = match self {
+            Self::Stretch | Self::Start => 0,
+            Self::Center => (available - size) / 2,
+            Self::End => available - size,
+        };
+
+        (offset, size)
     }
 }
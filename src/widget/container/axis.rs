@@ -1,15 +1,10 @@
-#[derive(Clone, Copy, Eq, PartialEq)]
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
 pub enum Axis {
     Horizontal,
+    #[default]
     Vertical,
 }
 
-impl Default for Axis {
-    fn default() -> Self {
-        Self::Vertical
-    }
-}
-
 impl Axis {
     pub fn opposite(&self) -> Axis {
         match self {
@@ -0,0 +1,93 @@
+use super::{Border, Inset, Insets};
+use crate::widget::offset::Offset;
+use cherry_macros::Builder;
+use embedded_graphics::{
+    prelude::*,
+    primitives::{CornerRadii, PrimitiveStyleBuilder, Rectangle, RoundedRectangle},
+};
+
+/// The colors and shape of a box: the background and border of a `Container`,
+/// or of a widget drawn like one.
+#[derive(Clone, Copy, Builder, Eq, PartialEq)]
+pub struct BoxStyle<Color> {
+    pub background_color: Option<Color>,
+    pub border: Option<Border<Color>>,
+    pub corner_radii: Option<CornerRadii>,
+}
+
+impl<Color> BoxStyle<Color>
+where
+    Color: PixelColor,
+{
+    pub fn new() -> Self {
+        Self {
+            background_color: None,
+            border: None,
+            corner_radii: None,
+        }
+    }
+
+    /// The style, with the fields that are `None` taken from `fallback`.
+    pub fn or(self, fallback: Self) -> Self {
+        Self {
+            background_color: self.background_color.or(fallback.background_color),
+            border: self.border.or(fallback.border),
+            corner_radii: self.corner_radii.or(fallback.corner_radii),
+        }
+    }
+
+    /// The part of `box_bounds` inside the border. The border is centered on
+    /// the edges, and its inner half gets the remainder when its width isn't
+    /// divisible by 2.
+    pub(crate) fn inner_bounds(&self, box_bounds: Rectangle) -> Rectangle {
+        let width = self.border.map_or(0, |border| border.width);
+        let inner_width = width / 2 + width % 2;
+
+        Rectangle::new(
+            box_bounds.top_left.offset(inner_width, inner_width),
+            box_bounds.size.inset(Insets::all(inner_width)),
+        )
+    }
+
+    /// Draws the part of the box that is inside `clip`. The border is centered
+    /// on the edges of `box_bounds`.
+    pub(crate) fn draw<Display>(
+        &self,
+        display: &mut Display,
+        box_bounds: Rectangle,
+        clip: Rectangle,
+    ) -> Result<(), Display::Error>
+    where
+        Display: DrawTarget<Color = Color>,
+    {
+        let mut style = PrimitiveStyleBuilder::new();
+
+        if let Some(background_color) = self.background_color {
+            style = style.fill_color(background_color);
+        }
+
+        if let Some(border) = self.border {
+            style = style.stroke_color(border.color).stroke_width(border.width);
+        }
+
+        let style = style.build();
+
+        let mut display = display.clipped(&clip);
+
+        match self.corner_radii {
+            Some(corner_radii) => RoundedRectangle::new(box_bounds, corner_radii)
+                .into_styled(style)
+                .draw(&mut display),
+            None => box_bounds.into_styled(style).draw(&mut display),
+        }
+    }
+}
+
+impl<Color> Default for BoxStyle<Color>
+where
+    Color: PixelColor,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
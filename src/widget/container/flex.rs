@@ -0,0 +1,239 @@
+use alloc::vec::Vec;
+
+/// A child's size along the main axis before any extra space is distributed,
+/// along with how it is allowed to flex.
+#[derive(Clone, Copy)]
+pub(super) struct FlexItem {
+    pub base: u32,
+    pub grow: u32,
+    pub shrink: u32,
+    pub min: Option<u32>,
+    pub max: Option<u32>,
+}
+
+impl FlexItem {
+    fn clamp(&self, value: u32) -> u32 {
+        let value = self.max.map_or(value, |max| value.min(max));
+        self.min.map_or(value, |min| value.max(min))
+    }
+}
+
+/// Distributes `extra` among the items in proportion to their grow factors.
+/// Items that would grow past their maximum are frozen at it, and the rest of
+/// the space is shared among the remaining items.
+///
+/// Returns the final sizes, and the space left over because every growable
+/// item reached its maximum (or there were none).
+pub(super) fn grow(items: &[FlexItem], mut extra: u32) -> (Vec<u32>, u32) {
+    let mut sizes: Vec<u32> = items.iter().map(|item| item.base).collect();
+    let mut frozen: Vec<bool> = items.iter().map(|item| item.grow == 0).collect();
+
+    loop {
+        let grow_total: u32 = items
+            .iter()
+            .zip(&frozen)
+            .filter(|(_, frozen)| !**frozen)
+            .fold(0, |total: u32, (item, _)| total.saturating_add(item.grow));
+
+        if grow_total == 0 {
+            return (sizes, extra);
+        }
+
+        let grow_unit = extra / grow_total;
+        let mut violated = false;
+
+        for (index, item) in items.iter().enumerate() {
+            if frozen[index] {
+                continue;
+            }
+
+            let target = item
+                .base
+                .saturating_add(grow_unit.saturating_mul(item.grow));
+            let clamped = item.clamp(target);
+
+            if clamped != target {
+                sizes[index] = clamped;
+                frozen[index] = true;
+                // an item can be clamped to more than the extra space (by its
+                // minimum), or to less than its base size (by its maximum)
+                extra = match clamped.checked_sub(item.base) {
+                    Some(grown) => extra.saturating_sub(grown),
+                    None => extra.saturating_add(item.base - clamped),
+                };
+                violated = true;
+            }
+        }
+
+        if !violated {
+            for (index, item) in items.iter().enumerate() {
+                if !frozen[index] {
+                    sizes[index] = item
+                        .base
+                        .saturating_add(grow_unit.saturating_mul(item.grow));
+                }
+            }
+
+            return (sizes, 0);
+        }
+    }
+}
+
+/// Removes `overflow` from the items in proportion to their shrink factors
+/// multiplied by their base sizes (as CSS flexbox does), so that larger items
+/// give up more space. Items that would shrink past their minimum (or zero)
+/// are frozen at it, and the rest of the overflow is shared among the
+/// remaining items.
+///
+/// Returns the final sizes, which may still overflow if every shrinkable item
+/// reached its minimum (or there were none).
+pub(super) fn shrink(items: &[FlexItem], mut overflow: u32) -> Vec<u32> {
+    let mut sizes: Vec<u32> = items.iter().map(|item| item.base).collect();
+    let mut frozen: Vec<bool> = items
+        .iter()
+        .map(|item| item.shrink == 0 || item.base == 0)
+        .collect();
+
+    loop {
+        let weight = |item: &FlexItem| item.shrink as u64 * item.base as u64;
+        let weight_total: u64 = items
+            .iter()
+            .zip(&frozen)
+            .filter(|(_, frozen)| !**frozen)
+            .map(|(item, _)| weight(item))
+            .sum();
+
+        if weight_total == 0 || overflow == 0 {
+            return sizes;
+        }
+
+        // the reductions are computed from the running total of the weights,
+        // so that rounding never leaves part of the overflow unassigned
+        let mut cumulative_weight = 0;
+        let mut assigned = 0;
+        let mut targets = Vec::with_capacity(items.len());
+        let mut removed_by_frozen = 0;
+        let mut violated = false;
+
+        for (index, item) in items.iter().enumerate() {
+            if frozen[index] {
+                targets.push(sizes[index]);
+                continue;
+            }
+
+            cumulative_weight += weight(item);
+            let total_reduction = (overflow as u64 * cumulative_weight / weight_total) as u32;
+            let reduction = total_reduction - assigned;
+            assigned = total_reduction;
+
+            let min = item.min.unwrap_or(0).min(item.base);
+
+            match item.base.checked_sub(reduction) {
+                Some(target) if target >= min => targets.push(target),
+                _ => {
+                    sizes[index] = min;
+                    frozen[index] = true;
+                    removed_by_frozen += item.base - min;
+                    violated = true;
+                    targets.push(min);
+                }
+            }
+        }
+
+        if !violated {
+            return targets;
+        }
+
+        overflow = overflow.saturating_sub(removed_by_frozen);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(base: u32, grow: u32, max: Option<u32>) -> FlexItem {
+        FlexItem {
+            base,
+            grow,
+            shrink: 0,
+            min: None,
+            max,
+        }
+    }
+
+    fn shrinkable(base: u32, shrink: u32, min: Option<u32>) -> FlexItem {
+        FlexItem {
+            base,
+            grow: 0,
+            shrink,
+            min,
+            max: None,
+        }
+    }
+
+    #[test]
+    fn grow_without_limits() {
+        let items = [item(10, 1, None), item(10, 2, None), item(10, 0, None)];
+        assert_eq!(grow(&items, 30), (vec![20, 30, 10], 0));
+    }
+
+    #[test]
+    fn grow_with_max() {
+        let items = [item(10, 1, Some(15)), item(10, 1, None)];
+        assert_eq!(grow(&items, 30), (vec![15, 35], 0));
+
+        let items = [item(10, 1, Some(15)), item(10, 1, Some(12))];
+        assert_eq!(grow(&items, 30), (vec![15, 12], 23));
+    }
+
+    #[test]
+    fn grow_with_min_past_extra() {
+        let items = [FlexItem {
+            min: Some(50),
+            ..item(0, 1, None)
+        }];
+        assert_eq!(grow(&items, 10), (vec![50], 0));
+    }
+
+    #[test]
+    fn grow_with_base_past_max() {
+        let items = [item(20, 1, Some(5)), item(0, 1, None)];
+        assert_eq!(grow(&items, 10), (vec![5, 25], 0));
+    }
+
+    #[test]
+    fn grow_without_growable_items() {
+        let items = [item(10, 0, None)];
+        assert_eq!(grow(&items, 30), (vec![10], 30));
+    }
+
+    #[test]
+    fn shrink_in_proportion_to_weights() {
+        let items = [shrinkable(40, 1, None), shrinkable(20, 1, None)];
+        assert_eq!(shrink(&items, 30), vec![20, 10]);
+
+        let items = [shrinkable(20, 1, None), shrinkable(20, 0, None)];
+        assert_eq!(shrink(&items, 10), vec![10, 20]);
+    }
+
+    #[test]
+    fn shrink_assigns_rounding_remainder() {
+        let items = [
+            shrinkable(10, 1, None),
+            shrinkable(10, 1, None),
+            shrinkable(10, 1, None),
+        ];
+        let sizes = shrink(&items, 10);
+        assert_eq!(sizes.iter().sum::<u32>(), 20);
+    }
+
+    #[test]
+    fn shrink_with_min() {
+        let items = [shrinkable(40, 1, Some(35)), shrinkable(20, 1, None)];
+        assert_eq!(shrink(&items, 30), vec![35, 0]);
+
+        let items = [shrinkable(40, 1, Some(35)), shrinkable(40, 1, None)];
+        assert_eq!(shrink(&items, 30), vec![35, 15]);
+    }
+}
@@ -1,24 +1,32 @@
 use embedded_graphics::prelude::*;
 
-#[derive(Clone, Copy, Eq, PartialEq)]
-pub struct Insets {
-    pub left: u32,
-    pub right: u32,
-    pub top: u32,
-    pub bottom: u32,
+use crate::widget::Length;
+
+/// Space around the edges of a rectangle, in pixels (`Insets<u32>`) or as
+/// lengths which are resolved during layout (`Insets<Length>`).
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub struct Insets<T = u32> {
+    pub left: T,
+    pub right: T,
+    pub top: T,
+    pub bottom: T,
 }
 
-impl Insets {
+impl<T> Insets<T>
+where
+    T: Copy + Default,
+{
     pub fn none() -> Self {
         Self {
-            left: 0,
-            right: 0,
-            top: 0,
-            bottom: 0,
+            left: T::default(),
+            right: T::default(),
+            top: T::default(),
+            bottom: T::default(),
         }
     }
 
-    pub fn all(value: u32) -> Self {
+    pub fn all(value: T) -> Self {
         Self {
             left: value,
             right: value,
@@ -27,31 +35,77 @@ impl Insets {
         }
     }
 
-    pub fn horizontal(value: u32) -> Self {
+    pub fn horizontal(value: T) -> Self {
         Self {
             left: value,
             right: value,
-            top: 0,
-            bottom: 0,
+            top: T::default(),
+            bottom: T::default(),
         }
     }
 
-    pub fn vertical(value: u32) -> Self {
+    pub fn vertical(value: T) -> Self {
         Self {
-            left: 0,
-            right: 0,
+            left: T::default(),
+            right: T::default(),
             top: value,
             bottom: value,
         }
     }
 }
 
-impl Default for Insets {
+impl Insets<Length> {
+    /// Resolves the insets against `available` space, where the left and right
+    /// insets are relative to its width and the top and bottom insets are
+    /// relative to its height. `Auto` insets are zero.
+    pub fn resolve(&self, available: Size) -> Insets {
+        let resolve = |length: Length, available: u32| length.resolve(Some(available)).unwrap_or(0);
+
+        Insets {
+            left: resolve(self.left, available.width),
+            right: resolve(self.right, available.width),
+            top: resolve(self.top, available.height),
+            bottom: resolve(self.bottom, available.height),
+        }
+    }
+}
+
+impl From<Insets> for Insets<Length> {
+    fn from(insets: Insets) -> Self {
+        Self {
+            left: insets.left.into(),
+            right: insets.right.into(),
+            top: insets.top.into(),
+            bottom: insets.bottom.into(),
+        }
+    }
+}
+
+impl Insets {
+    pub fn horizontal_total(&self) -> u32 {
+        self.left.saturating_add(self.right)
+    }
+
+    pub fn vertical_total(&self) -> u32 {
+        self.top.saturating_add(self.bottom)
+    }
+
+    pub fn total(&self) -> Size {
+        Size::new(self.horizontal_total(), self.vertical_total())
+    }
+}
+
+impl<T> Default for Insets<T>
+where
+    T: Copy + Default,
+{
     fn default() -> Self {
         Self::none()
     }
 }
 
+/// Insetting saturates at zero and outsetting saturates at `u32::MAX`, so
+/// insets that are larger than the size never panic or wrap around.
 pub trait Inset {
     fn inset(&self, insets: Insets) -> Self;
     fn outset(&self, insets: Insets) -> Self;
@@ -60,15 +114,40 @@ pub trait Inset {
 impl Inset for Size {
     fn inset(&self, insets: Insets) -> Self {
         Self::new(
-            self.width - insets.left - insets.right,
-            self.height - insets.top - insets.bottom,
+            self.width.saturating_sub(insets.horizontal_total()),
+            self.height.saturating_sub(insets.vertical_total()),
         )
     }
 
     fn outset(&self, insets: Insets) -> Self {
         Self::new(
-            self.width + insets.left + insets.right,
-            self.height + insets.top + insets.bottom,
+            self.width.saturating_add(insets.horizontal_total()),
+            self.height.saturating_add(insets.vertical_total()),
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve() {
+        let insets = Insets {
+            left: Length::Percent(10),
+            right: Length::Px(3),
+            top: Length::Auto,
+            bottom: Length::Percent(50),
+        };
+
+        assert_eq!(
+            insets.resolve(Size::new(200, 20)),
+            Insets {
+                left: 20,
+                right: 3,
+                top: 0,
+                bottom: 10,
+            }
+        );
+    }
+}
@@ -1,5 +1,6 @@
-#[derive(Clone, Copy, Eq, PartialEq)]
+#[derive(Clone, Copy, Default, Eq, PartialEq)]
 pub enum Justification {
+    #[default]
     Start,
     Center,
     End,
@@ -8,8 +9,30 @@ pub enum Justification {
     SpaceEvenly,
 }
 
-impl Default for Justification {
-    fn default() -> Self {
-        Self::Start
+impl Justification {
+    /// Returns the offset of the first item and the space between items when
+    /// `count` items share `unused` space.
+    pub(super) fn offsets(&self, unused: u32, count: u32) -> (u32, u32) {
+        if count == 0 {
+            return (0, 0);
+        }
+
+        match self {
+            Self::Start => (0, 0),
+            Self::Center => (unused / 2, 0),
+            Self::End => (unused, 0),
+            Self::SpaceBetween => {
+                let space = if count > 1 { unused / (count - 1) } else { 0 };
+                (0, space)
+            }
+            Self::SpaceAround => {
+                let space = unused / count;
+                (space / 2, space)
+            }
+            Self::SpaceEvenly => {
+                let space = unused / (count + 1);
+                (space, space)
+            }
+        }
     }
 }
@@ -0,0 +1,36 @@
+use super::Justification;
+
+/// How the lines of a wrapping `Container` are packed along the cross axis.
+#[derive(Clone, Copy, Default, Eq, PartialEq)]
+pub enum LineAlignment {
+    #[default]
+    Start,
+    Center,
+    End,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+    Stretch,
+}
+
+impl LineAlignment {
+    /// Returns the offset of the first line, the space between lines, and the
+    /// amount added to each line's cross axis dimension when `count` lines
+    /// share `unused` space.
+    pub(super) fn offsets(&self, unused: u32, count: u32) -> (u32, u32, u32) {
+        let justification = match self {
+            Self::Start => Justification::Start,
+            Self::Center => Justification::Center,
+            Self::End => Justification::End,
+            Self::SpaceBetween => Justification::SpaceBetween,
+            Self::SpaceAround => Justification::SpaceAround,
+            Self::SpaceEvenly => Justification::SpaceEvenly,
+            Self::Stretch => {
+                return (0, 0, unused.checked_div(count).unwrap_or(0));
+            }
+        };
+
+        let (start, space) = justification.offsets(unused, count);
+        (start, space, 0)
+    }
+}
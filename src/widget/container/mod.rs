@@ -1,22 +1,37 @@
 mod alignment;
 mod axis;
 mod border;
+mod box_style;
+mod flex;
 mod insets;
 mod justification;
+mod line_alignment;
 
 pub use alignment::Alignment;
 pub use axis::Axis;
 pub use border::Border;
+pub use box_style::BoxStyle;
 pub use insets::{Inset, Insets};
 pub use justification::Justification;
+pub use line_alignment::LineAlignment;
 
-use super::{axis_size::AxisSize, IntrinsicSize, LayoutOptions, Widget};
+use super::{
+    axis_size::AxisSize,
+    offset::{to_i32, Offset},
+    IntrinsicSize, LayoutNode, LayoutOptions, Length, Measurement, Position, Widget,
+};
 use alloc::{boxed::Box, vec::Vec};
 use cherry_macros::Builder;
+use core::{
+    any::Any,
+    hash::{Hash, Hasher},
+    ops::Range,
+};
 use embedded_graphics::{
     prelude::*,
-    primitives::{CornerRadii, PrimitiveStyleBuilder, Rectangle, RoundedRectangle},
+    primitives::{CornerRadii, Rectangle},
 };
+use flex::FlexItem;
 
 #[derive(Builder)]
 pub struct Container<Display>
@@ -25,16 +40,38 @@ where
 {
     alignment: Alignment,
     axis: Axis,
+    /// A widget drawn in the box, on top of the background color and below
+    /// the border (such as an `image::NinePatch`).
+    background: Option<Box<dyn Widget<Display>>>,
     background_color: Option<Display::Color>,
     border: Option<Border<Display::Color>>,
     children: Vec<Box<dyn Widget<Display>>>,
+    column_gap: u32,
     corner_radii: Option<CornerRadii>,
-    height: Option<u32>,
+    /// Drawn instead of the border while the container has focus. It doesn't
+    /// change the layout, so a focus border that is wider than the border is
+    /// drawn over the padding.
+    focus_border: Option<Border<Display::Color>>,
+    focusable: bool,
+    #[omit]
+    focused: bool,
+    #[into]
+    height: Length,
     justification: Justification,
     layout_options: LayoutOptions,
-    margin: Insets,
-    padding: Insets,
-    width: Option<u32>,
+    line_alignment: LineAlignment,
+    #[into]
+    margin: Insets<Length>,
+    max_height: Option<u32>,
+    max_width: Option<u32>,
+    min_height: Option<u32>,
+    min_width: Option<u32>,
+    #[into]
+    padding: Insets<Length>,
+    row_gap: u32,
+    #[into]
+    width: Length,
+    wrap: bool,
 }
 
 impl<Display> Default for Container<Display>
@@ -54,19 +91,36 @@ where
         Self {
             alignment: Default::default(),
             axis: Default::default(),
+            background: Default::default(),
             background_color: Default::default(),
             border: Default::default(),
             children: Default::default(),
+            column_gap: Default::default(),
             corner_radii: Default::default(),
+            focus_border: Default::default(),
+            focusable: Default::default(),
+            focused: Default::default(),
             height: Default::default(),
             justification: Default::default(),
             layout_options: Default::default(),
+            line_alignment: Default::default(),
             margin: Default::default(),
+            max_height: Default::default(),
+            max_width: Default::default(),
+            min_height: Default::default(),
+            min_width: Default::default(),
             padding: Default::default(),
+            row_gap: Default::default(),
             width: Default::default(),
+            wrap: Default::default(),
         }
     }
 
+    /// Sets both the column gap and the row gap.
+    pub fn gap(self, gap: u32) -> Self {
+        self.column_gap(gap).row_gap(gap)
+    }
+
     fn main_axis(&self) -> Axis {
         self.axis
     }
@@ -75,166 +129,556 @@ where
         self.axis.opposite()
     }
 
-    fn content_size(&self) -> IntrinsicSize {
+    fn gap_for_axis(&self, axis: Axis) -> u32 {
+        match axis {
+            Axis::Horizontal => self.column_gap,
+            Axis::Vertical => self.row_gap,
+        }
+    }
+
+    /// The total space taken by the gaps between `count` items along `axis`.
+    fn total_gap(&self, count: usize, axis: Axis) -> u32 {
+        self.gap_for_axis(axis)
+            .saturating_mul(count.max(1) as u32 - 1)
+    }
+
+    /// The children's intrinsic sizes, after applying their widths and heights
+    /// (resolved against the `available` content size, when it is known) and
+    /// clamping them to their minimum and maximum sizes.
+    fn child_sizes(&self, children: &[Measurement], available: Option<Size>) -> Vec<IntrinsicSize> {
         self.children
             .iter()
-            .fold(IntrinsicSize::none(), |size, widget| {
-                let widget_size = widget.intrinsic_size();
+            .zip(children)
+            .map(|(child, measurement)| {
+                child
+                    .layout_options()
+                    .resolve_intrinsic_size(measurement.intrinsic_size, available)
+            })
+            .collect()
+    }
 
-                let cross_axis_dimension = match (
-                    size.for_axis(self.cross_axis()),
-                    widget_size.for_axis(self.cross_axis()),
-                ) {
-                    (Some(size), Some(widget_size)) => Some(size.max(widget_size)),
-                    (Some(size), None) => Some(size),
-                    (None, Some(widget_size)) => Some(widget_size),
-                    (None, None) => None,
-                };
+    /// The sizes from `child_sizes` of the children that are laid out along
+    /// the main axis.
+    fn flow_sizes(&self, children: &[Measurement], available: Option<Size>) -> Vec<IntrinsicSize> {
+        let child_sizes = self.child_sizes(children, available);
+        self.flow()
+            .into_iter()
+            .map(|index| child_sizes[index])
+            .collect()
+    }
 
-                let main_axis_dimension = match (
-                    size.for_axis(self.main_axis()),
-                    widget_size.for_axis(self.main_axis()),
-                ) {
-                    (Some(size), Some(widget_size)) => Some(size + widget_size),
-                    (Some(size), None) => Some(size),
-                    (None, Some(widget_size)) => Some(widget_size),
-                    (None, None) => None,
-                };
+    /// The indices of the children that are laid out along the main axis,
+    /// which excludes absolutely positioned children.
+    fn flow(&self) -> Vec<usize> {
+        self.children
+            .iter()
+            .enumerate()
+            .filter(|(_, child)| child.layout_options().position != Position::Absolute)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    fn content_size(&self, child_sizes: &[IntrinsicSize]) -> IntrinsicSize {
+        let mut content_size =
+            child_sizes
+                .iter()
+                .fold(IntrinsicSize::none(), |size, &widget_size| {
+                    let cross_axis_dimension = match (
+                        size.for_axis(self.cross_axis()),
+                        widget_size.for_axis(self.cross_axis()),
+                    ) {
+                        (Some(size), Some(widget_size)) => Some(size.max(widget_size)),
+                        (Some(size), None) => Some(size),
+                        (None, Some(widget_size)) => Some(widget_size),
+                        (None, None) => None,
+                    };
+
+                    let main_axis_dimension = match (
+                        size.for_axis(self.main_axis()),
+                        widget_size.for_axis(self.main_axis()),
+                    ) {
+                        (Some(size), Some(widget_size)) => Some(size.saturating_add(widget_size)),
+                        (Some(size), None) => Some(size),
+                        (None, Some(widget_size)) => Some(widget_size),
+                        (None, None) => None,
+                    };
 
-                match self.main_axis() {
-                    Axis::Horizontal => {
-                        IntrinsicSize::new(main_axis_dimension, cross_axis_dimension)
+                    match self.main_axis() {
+                        Axis::Horizontal => {
+                            IntrinsicSize::new(main_axis_dimension, cross_axis_dimension)
+                        }
+                        Axis::Vertical => {
+                            IntrinsicSize::new(cross_axis_dimension, main_axis_dimension)
+                        }
                     }
-                    Axis::Vertical => IntrinsicSize::new(cross_axis_dimension, main_axis_dimension),
-                }
-            })
+                });
+
+        content_size.add_to_axis(
+            self.total_gap(child_sizes.len(), self.main_axis()),
+            self.main_axis(),
+        );
+        content_size
     }
 
     fn border_width(&self) -> u32 {
         self.border.map_or(0, |border| border.width)
     }
 
+    /// The rectangle containing the background and border, which is the
+    /// container's bounds inset by the margin and the outer half of the border.
+    /// Percentage margins are relative to the size of the bounds.
+    fn box_bounds(&self, bounds: Rectangle) -> Rectangle {
+        let margin = self.margin.resolve(bounds.size);
+        let box_origin = bounds.top_left.offset(margin.left, margin.top);
+
+        // outer half of the border
+        let outer_border = Insets::all(self.border_width() / 2);
+
+        let box_size = bounds.size.inset(margin).inset(outer_border);
+        Rectangle::new(box_origin, box_size)
+    }
+
+    /// The rectangle the children are laid out in, which is the box inset by
+    /// the inner half of the border and the padding. Percentage padding is
+    /// relative to the size of the bounds.
+    fn content_bounds(&self, bounds: Rectangle) -> Rectangle {
+        let box_bounds = self.box_bounds(bounds);
+        let padding = self.padding.resolve(bounds.size);
+
+        // inner half of the border (the inner half gets the remainder
+        // when the border width isn't divisble by 2)
+        let inner_border_width = self.border_width() / 2 + self.border_width() % 2;
+        let inner_border = Insets::all(inner_border_width);
+
+        let content_origin = box_bounds.top_left.offset(
+            inner_border_width.saturating_add(padding.left),
+            inner_border_width.saturating_add(padding.top),
+        );
+        let content_size = box_bounds.size.inset(inner_border).inset(padding);
+        Rectangle::new(content_origin, content_size)
+    }
+
     fn draw_self(
         &self,
         display: &mut Display,
-        origin: Point,
-        size: Size,
+        box_bounds: Rectangle,
+        clip: Rectangle,
     ) -> Result<(), Display::Error> {
-        let mut style = PrimitiveStyleBuilder::new();
+        let border = if self.focused {
+            self.focus_border.or(self.border)
+        } else {
+            self.border
+        };
 
-        if let Some(background_color) = self.background_color {
-            style = style.fill_color(background_color);
-        }
+        let style = BoxStyle {
+            background_color: self.background_color,
+            border,
+            corner_radii: self.corner_radii,
+        };
 
-        if let Some(border) = self.border {
-            style = style.stroke_color(border.color).stroke_width(border.width);
+        match &self.background {
+            Some(background) => {
+                BoxStyle {
+                    border: None,
+                    ..style
+                }
+                .draw(display, box_bounds, clip)?;
+                background.draw_clipped(display, &background.layout(box_bounds), clip)?;
+                BoxStyle {
+                    background_color: None,
+                    ..style
+                }
+                .draw(display, box_bounds, clip)
+            }
+            None => style.draw(display, box_bounds, clip),
         }
+    }
 
-        let style = style.build();
-        let rectangle = Rectangle::new(origin, size);
-
-        match self.corner_radii {
-            Some(corner_radii) => RoundedRectangle::new(rectangle, corner_radii)
-                .into_styled(style)
-                .draw(display),
-            None => rectangle.into_styled(style).draw(display),
-        }
+    /// Replaces the background color, border and corner radii, for widgets
+    /// that restyle a container when their state changes.
+    pub(crate) fn set_box_style(&mut self, style: BoxStyle<Display::Color>) {
+        self.background_color = style.background_color;
+        self.border = style.border;
+        self.corner_radii = style.corner_radii;
     }
 
-    fn draw_children(
+    /// A child's size along the cross axis of a line that is `available` wide.
+    /// Children without an intrinsic size fill the line if they are stretched.
+    fn clamp_cross_axis_dimension(
         &self,
-        display: &mut Display,
-        origin: Point,
-        size: Size,
-    ) -> Result<(), Display::Error> {
-        let num_children = self.children.len() as u32;
+        layout_options: &LayoutOptions,
+        intrinsic_dimension: Option<u32>,
+        alignment: Alignment,
+        available: u32,
+    ) -> u32 {
+        let default_dimension = match alignment {
+            Alignment::Stretch => available,
+            _ => 0,
+        };
 
-        if num_children == 0 {
-            return Ok(());
+        layout_options
+            .clamp_for_axis(
+                intrinsic_dimension.unwrap_or(default_dimension),
+                self.cross_axis(),
+            )
+            .min(available)
+    }
+
+    /// The height of `child` when it is `width` wide, for children whose height
+    /// depends on their width and that don't have a height of their own.
+    fn child_height_for_width(
+        child: &dyn Widget<Display>,
+        measurement: &Measurement,
+        width: u32,
+    ) -> Option<u32> {
+        let layout_options = child.layout_options();
+
+        match layout_options.height {
+            Length::Auto => child
+                .height_for_width(measurement, width)
+                .map(|height| layout_options.clamp_for_axis(height, Axis::Vertical)),
+            _ => None,
         }
+    }
 
-        let total_children_main_axis_dimension = self
-            .content_size()
-            .for_axis(self.main_axis())
-            .unwrap_or(0)
-            .min(size.for_axis(self.main_axis()));
-        let extra_main_axis_dimension =
-            size.for_axis(self.main_axis()) - total_children_main_axis_dimension;
-        let grow_total: u32 = self
-            .children
-            .iter()
-            .map(|child| child.layout_options().grow)
-            .sum();
+    /// Converts main and cross axis offsets into a point relative to `origin`.
+    fn offset_point(&self, origin: Point, main_axis_offset: u32, cross_axis_offset: u32) -> Point {
+        match self.main_axis() {
+            Axis::Horizontal => origin.offset(main_axis_offset, cross_axis_offset),
+            Axis::Vertical => origin.offset(cross_axis_offset, main_axis_offset),
+        }
+    }
+
+    fn arrange_children(
+        &self,
+        children: &[Measurement],
+        content_bounds: Rectangle,
+    ) -> Vec<LayoutNode> {
+        let child_sizes = self.child_sizes(children, Some(content_bounds.size));
+        let flow = self.flow();
+        let mut nodes: Vec<Option<LayoutNode>> = self.children.iter().map(|_| None).collect();
 
-        let (unused_main_axis_dimension, grow_unit) = if grow_total > 0 {
-            (0, extra_main_axis_dimension / grow_total)
+        let lines = if self.wrap {
+            let flow_sizes: Vec<IntrinsicSize> =
+                flow.iter().map(|&index| child_sizes[index]).collect();
+            self.lines(&flow_sizes, content_bounds.size.for_axis(self.main_axis()))
         } else {
-            (extra_main_axis_dimension, 0)
+            core::iter::once(0..flow.len()).collect()
         };
 
-        let (mut current_main_axis_pos, space) = match self.justification {
-            Justification::Start => (0, 0),
-            Justification::Center => (unused_main_axis_dimension / 2, 0),
-            Justification::End => (unused_main_axis_dimension, 0),
-            Justification::SpaceBetween => {
-                let space = if num_children > 1 {
-                    unused_main_axis_dimension / (num_children - 1)
-                } else {
-                    0
-                };
-                (0, space)
-            }
-            Justification::SpaceAround => {
-                let space = unused_main_axis_dimension / num_children;
-                (space / 2, space)
+        let line_cross_axis_dimensions: Vec<u32> = if self.wrap {
+            lines
+                .iter()
+                .map(|line| {
+                    flow[line.clone()]
+                        .iter()
+                        .filter_map(|&index| child_sizes[index].for_axis(self.cross_axis()))
+                        .max()
+                        .unwrap_or(0)
+                })
+                .collect()
+        } else {
+            Vec::from([content_bounds.size.for_axis(self.cross_axis())])
+        };
+
+        let total_cross_axis_dimension = line_cross_axis_dimensions
+            .iter()
+            .fold(0, |total: u32, dimension| total.saturating_add(*dimension))
+            .saturating_add(self.total_gap(lines.len(), self.cross_axis()));
+        let unused_cross_axis_dimension = content_bounds
+            .size
+            .for_axis(self.cross_axis())
+            .saturating_sub(total_cross_axis_dimension);
+        let (mut current_cross_axis_pos, space, extra_cross_axis_dimension) = self
+            .line_alignment
+            .offsets(unused_cross_axis_dimension, lines.len() as u32);
+
+        for (line, line_cross_axis_dimension) in lines.into_iter().zip(line_cross_axis_dimensions) {
+            let line_origin = self.offset_point(content_bounds.top_left, 0, current_cross_axis_pos);
+            let mut line_size = Size::zero();
+            line_size.add_to_axis(
+                content_bounds.size.for_axis(self.main_axis()),
+                self.main_axis(),
+            );
+            line_size.add_to_axis(
+                line_cross_axis_dimension.saturating_add(extra_cross_axis_dimension),
+                self.cross_axis(),
+            );
+
+            let line_nodes = self.arrange_line(
+                &flow[line.clone()],
+                children,
+                &child_sizes,
+                Rectangle::new(line_origin, line_size),
+            );
+
+            for (&index, node) in flow[line].iter().zip(line_nodes) {
+                nodes[index] = Some(node);
             }
-            Justification::SpaceEvenly => {
-                let space = unused_main_axis_dimension / (num_children + 1);
-                (space, space)
+
+            current_cross_axis_pos = current_cross_axis_pos
+                .saturating_add(line_size.for_axis(self.cross_axis()))
+                .saturating_add(self.gap_for_axis(self.cross_axis()))
+                .saturating_add(space);
+        }
+
+        self.children
+            .iter()
+            .zip(children)
+            .zip(child_sizes)
+            .zip(nodes)
+            .map(|(((child, measurement), child_size), node)| {
+                node.unwrap_or_else(|| {
+                    self.arrange_absolute(child.as_ref(), measurement, child_size, content_bounds)
+                })
+            })
+            .collect()
+    }
+
+    /// Lays out an absolutely positioned child using its offsets from the
+    /// edges of the content box. A child with offsets from both edges of an
+    /// axis is sized to fit between them, and a child without an intrinsic
+    /// size fills the space from its offset to the opposite edge.
+    fn arrange_absolute(
+        &self,
+        child: &dyn Widget<Display>,
+        measurement: &Measurement,
+        child_size: IntrinsicSize,
+        content_bounds: Rectangle,
+    ) -> LayoutNode {
+        let layout_options = child.layout_options();
+
+        let place = |axis: Axis| {
+            let available = to_i32(content_bounds.size.for_axis(axis));
+            let (start, end) = layout_options.offsets_for_axis(axis);
+            let clamp = |size: i32| layout_options.clamp_for_axis(size.max(0) as u32, axis);
+            let intrinsic_size = child_size.for_axis(axis).map(to_i32);
+
+            match (start, end) {
+                (Some(start), Some(end)) => (
+                    start,
+                    clamp(available.saturating_sub(start).saturating_sub(end)),
+                ),
+                (Some(start), None) => (
+                    start,
+                    clamp(intrinsic_size.unwrap_or(available.saturating_sub(start))),
+                ),
+                (None, Some(end)) => {
+                    let size = clamp(intrinsic_size.unwrap_or(available.saturating_sub(end)));
+                    (
+                        available.saturating_sub(end).saturating_sub(to_i32(size)),
+                        size,
+                    )
+                }
+                (None, None) => (0, clamp(intrinsic_size.unwrap_or(available))),
             }
         };
 
-        for child in &self.children {
-            let child_alignment = child.layout_options().alignment.unwrap_or(self.alignment);
+        let (x, width) = place(Axis::Horizontal);
+        let (y, height) = place(Axis::Vertical);
+
+        child.arrange(
+            measurement,
+            Rectangle::new(
+                content_bounds.top_left.saturating_add(Point::new(x, y)),
+                Size::new(width, height),
+            ),
+        )
+    }
+
+    /// Breaks the children into lines, moving a child onto a new line when it
+    /// doesn't fit in the remaining main axis space. Every line contains at
+    /// least one child.
+    fn lines(&self, child_sizes: &[IntrinsicSize], available: u32) -> Vec<Range<usize>> {
+        let gap = self.gap_for_axis(self.main_axis());
+        let mut lines = Vec::new();
+        let mut start = 0;
+        let mut line_main_axis_dimension: u32 = 0;
+
+        for (index, size) in child_sizes.iter().enumerate() {
+            let main_axis_dimension = size.for_axis(self.main_axis()).unwrap_or(0);
 
-            let default_size = match (child_alignment, self.main_axis()) {
-                (Alignment::Stretch, Axis::Horizontal) => Size::new(0, size.height),
-                (Alignment::Stretch, Axis::Vertical) => Size::new(size.width, 0),
-                _ => Size::zero(),
+            let extended_main_axis_dimension = line_main_axis_dimension
+                .saturating_add(gap)
+                .saturating_add(main_axis_dimension);
+
+            if index > start && extended_main_axis_dimension > available {
+                lines.push(start..index);
+                start = index;
+            }
+
+            line_main_axis_dimension = if index == start {
+                main_axis_dimension
+            } else {
+                extended_main_axis_dimension
             };
+        }
 
-            let mut child_size = child
-                .intrinsic_size()
-                .to_size_with_defaults(default_size)
-                .component_min(size);
-            child_size.add_to_axis(grow_unit * child.layout_options().grow, self.main_axis());
+        if start < child_sizes.len() {
+            lines.push(start..child_sizes.len());
+        }
 
-            let cross_axis_offset = match child_alignment {
-                Alignment::Stretch | Alignment::Start => 0,
-                Alignment::Center => {
-                    (size.for_axis(self.cross_axis()) - child_size.for_axis(self.cross_axis())) / 2
-                }
-                Alignment::End => {
-                    size.for_axis(self.cross_axis()) - child_size.for_axis(self.cross_axis())
+        lines
+    }
+
+    /// Lays out a single line of children along the main axis, returning the
+    /// layouts in the same order as `indices`.
+    fn arrange_line(
+        &self,
+        indices: &[usize],
+        measurements: &[Measurement],
+        child_sizes: &[IntrinsicSize],
+        line_bounds: Rectangle,
+    ) -> Vec<LayoutNode> {
+        let num_children = indices.len() as u32;
+
+        if num_children == 0 {
+            return Vec::new();
+        }
+
+        let size = line_bounds.size;
+
+        let flex_items: Vec<FlexItem> = indices
+            .iter()
+            .map(|&index| {
+                let layout_options = self.children[index].layout_options();
+                let child_size = child_sizes[index];
+                let child_alignment = layout_options.alignment.unwrap_or(self.alignment);
+
+                // a child that is stretched across the line, but has no size
+                // of its own, takes its main axis size from its aspect ratio
+                let base = match (
+                    child_size.for_axis(self.main_axis()),
+                    child_size.for_axis(self.cross_axis()),
+                    layout_options.aspect_ratio,
+                    child_alignment,
+                ) {
+                    (None, None, Some(ratio), Alignment::Stretch) => ratio.dimension_for_axis(
+                        layout_options
+                            .clamp_for_axis(size.for_axis(self.cross_axis()), self.cross_axis()),
+                        self.main_axis(),
+                    ),
+                    (base, ..) => base.unwrap_or(0),
+                };
+
+                // a child whose height depends on its width gets the height it
+                // needs at the width it will be laid out with
+                let base = match (self.main_axis(), layout_options.aspect_ratio) {
+                    (Axis::Vertical, None) => {
+                        let width = self.clamp_cross_axis_dimension(
+                            &layout_options,
+                            child_size.width,
+                            child_alignment,
+                            size.width,
+                        );
+                        Self::child_height_for_width(
+                            self.children[index].as_ref(),
+                            &measurements[index],
+                            width,
+                        )
+                        .unwrap_or(base)
+                    }
+                    _ => base,
+                };
+
+                FlexItem {
+                    base: layout_options.clamp_for_axis(base, self.main_axis()),
+                    grow: layout_options.grow,
+                    shrink: layout_options.shrink,
+                    min: layout_options.min_for_axis(self.main_axis()),
+                    max: layout_options.max_for_axis(self.main_axis()),
                 }
-            };
+            })
+            .collect();
 
-            let child_origin = match self.main_axis() {
-                Axis::Horizontal => Point::new(
-                    origin.x + current_main_axis_pos as i32,
-                    origin.y + cross_axis_offset as i32,
-                ),
-                Axis::Vertical => Point::new(
-                    origin.x + cross_axis_offset as i32,
-                    origin.y + current_main_axis_pos as i32,
-                ),
+        let available_main_axis_dimension = size
+            .for_axis(self.main_axis())
+            .saturating_sub(self.total_gap(indices.len(), self.main_axis()));
+        let total_children_main_axis_dimension = flex_items
+            .iter()
+            .fold(0, |total: u32, item| total.saturating_add(item.base));
+
+        let (main_axis_dimensions, unused_main_axis_dimension) =
+            if total_children_main_axis_dimension > available_main_axis_dimension {
+                let overflow = total_children_main_axis_dimension - available_main_axis_dimension;
+                let dimensions = flex::shrink(&flex_items, overflow)
+                    .into_iter()
+                    .map(|dimension| dimension.min(size.for_axis(self.main_axis())))
+                    .collect();
+
+                (dimensions, 0)
+            } else {
+                flex::grow(
+                    &flex_items,
+                    available_main_axis_dimension - total_children_main_axis_dimension,
+                )
             };
 
-            child.draw(display, child_origin, child_size)?;
-            current_main_axis_pos += child_size.for_axis(self.main_axis()) + space;
-        }
+        let (mut current_main_axis_pos, space) = self
+            .justification
+            .offsets(unused_main_axis_dimension, num_children);
 
-        Ok(())
+        indices
+            .iter()
+            .zip(main_axis_dimensions)
+            .map(|(&index, main_axis_dimension)| {
+                let child = &self.children[index];
+                let measurement = &measurements[index];
+                let intrinsic_size = child_sizes[index];
+                let layout_options = child.layout_options();
+                let child_alignment = layout_options.alignment.unwrap_or(self.alignment);
+
+                let intrinsic_cross_axis_dimension = match self.cross_axis() {
+                    Axis::Horizontal => intrinsic_size.width,
+                    Axis::Vertical => Self::child_height_for_width(
+                        child.as_ref(),
+                        measurement,
+                        main_axis_dimension,
+                    )
+                    .or(intrinsic_size.height),
+                };
+                let intrinsic_cross_axis_dimension = match layout_options.aspect_ratio {
+                    Some(ratio) => intrinsic_cross_axis_dimension.or_else(|| {
+                        Some(ratio.dimension_for_axis(main_axis_dimension, self.cross_axis()))
+                    }),
+                    None => intrinsic_cross_axis_dimension,
+                };
+                let cross_axis_dimension = self.clamp_cross_axis_dimension(
+                    &layout_options,
+                    intrinsic_cross_axis_dimension,
+                    child_alignment,
+                    size.for_axis(self.cross_axis()),
+                );
+
+                let mut child_size = Size::zero();
+                child_size.add_to_axis(main_axis_dimension, self.main_axis());
+                child_size.add_to_axis(cross_axis_dimension, self.cross_axis());
+
+                let cross_axis_offset = match child_alignment {
+                    Alignment::Stretch | Alignment::Start => 0,
+                    Alignment::Center => {
+                        (size.for_axis(self.cross_axis()) - cross_axis_dimension) / 2
+                    }
+                    Alignment::End => size.for_axis(self.cross_axis()) - cross_axis_dimension,
+                };
+
+                let mut child_origin = self.offset_point(
+                    line_bounds.top_left,
+                    current_main_axis_pos,
+                    cross_axis_offset,
+                );
+
+                if layout_options.position == Position::Relative {
+                    child_origin = child_origin.saturating_add(layout_options.relative_offset());
+                }
+
+                current_main_axis_pos = current_main_axis_pos
+                    .saturating_add(main_axis_dimension)
+                    .saturating_add(self.gap_for_axis(self.main_axis()))
+                    .saturating_add(space);
+                child.arrange(measurement, Rectangle::new(child_origin, child_size))
+            })
+            .collect()
     }
 }
 
@@ -243,63 +687,607 @@ where
     Display: DrawTarget,
 {
     fn intrinsic_size(&self) -> IntrinsicSize {
+        self.measure().intrinsic_size
+    }
+
+    fn layout_options(&self) -> LayoutOptions {
+        let length = |length: Length, default: Length| match length {
+            Length::Auto => default,
+            length => length,
+        };
+
+        LayoutOptions {
+            height: length(self.height, self.layout_options.height),
+            max_height: self.max_height.or(self.layout_options.max_height),
+            max_width: self.max_width.or(self.layout_options.max_width),
+            min_height: self.min_height.or(self.layout_options.min_height),
+            min_width: self.min_width.or(self.layout_options.min_width),
+            width: length(self.width, self.layout_options.width),
+            ..self.layout_options
+        }
+    }
+
+    /// Only vertical containers that don't wrap have a height that depends on
+    /// their width, when any of their children do. Vertical percentage margins
+    /// and padding don't count, because the height isn't known yet.
+    fn height_for_width(&self, measurement: &Measurement, width: u32) -> Option<u32> {
+        if self.axis != Axis::Vertical || self.wrap {
+            return None;
+        }
+
+        let bounds = Rectangle::new(Point::zero(), Size::new(width, 0));
+        let content_width = self.content_bounds(bounds).size.width;
+        let child_sizes = self.child_sizes(&measurement.children, None);
+        let flow = self.flow();
+        let mut depends_on_width = false;
+
+        let content_height = flow.iter().fold(0, |total: u32, &index| {
+            let child = &self.children[index];
+            let layout_options = child.layout_options();
+            let child_width = self.clamp_cross_axis_dimension(
+                &layout_options,
+                child_sizes[index].width,
+                layout_options.alignment.unwrap_or(self.alignment),
+                content_width,
+            );
+
+            let height = match Self::child_height_for_width(
+                child.as_ref(),
+                &measurement.children[index],
+                child_width,
+            ) {
+                Some(height) => {
+                    depends_on_width = true;
+                    height
+                }
+                None => child_sizes[index].height.unwrap_or(0),
+            };
+
+            total.saturating_add(height)
+        });
+
+        if !depends_on_width {
+            return None;
+        }
+
+        let height = content_height
+            .saturating_add(self.total_gap(flow.len(), Axis::Vertical))
+            .saturating_add(self.margin.resolve(bounds.size).vertical_total())
+            .saturating_add(self.padding.resolve(bounds.size).vertical_total())
+            .saturating_add(self.border_width().saturating_mul(2));
+
+        Some(self.layout_options().clamp_for_axis(height, Axis::Vertical))
+    }
+
+    fn measure(&self) -> Measurement {
+        self.measure_with_children(self.children.iter().map(|child| child.measure()).collect())
+    }
+
+    fn measure_with_children(&self, children: Vec<Measurement>) -> Measurement {
+        // percentages can't be resolved without knowing the available space,
+        // so only pixel margins and padding contribute to the intrinsic size
         let total_size = self
-            .content_size()
-            .outset(self.padding)
+            .content_size(&self.flow_sizes(&children, None))
+            .outset(self.padding.resolve(Size::zero()))
             .outset(Insets::all(self.border_width()))
-            .outset(self.margin);
+            .outset(self.margin.resolve(Size::zero()));
 
-        IntrinsicSize::new(
-            self.width.or(total_size.width),
-            self.height.or(total_size.height),
-        )
+        let intrinsic_size = self
+            .layout_options()
+            .clamp_intrinsic_size(IntrinsicSize::new(
+                self.width.resolve(None).or(total_size.width),
+                self.height.resolve(None).or(total_size.height),
+            ));
+
+        Measurement::with_children(intrinsic_size, children)
     }
 
-    fn layout_options(&self) -> LayoutOptions {
-        self.layout_options
+    fn arrange(&self, measurement: &Measurement, bounds: Rectangle) -> LayoutNode {
+        let content_bounds = self.content_bounds(bounds);
+        let children = self.arrange_children(&measurement.children, content_bounds);
+
+        let insets = self
+            .margin
+            .resolve(bounds.size)
+            .total()
+            .outset(self.padding.resolve(bounds.size))
+            .outset(Insets::all(self.border_width()));
+        let insets_overflow = IntrinsicSize::from(insets).overflow(bounds.size);
+
+        let node = LayoutNode::with_children(bounds, children);
+        let overflow = node
+            .children_overflow(content_bounds)
+            .component_max(insets_overflow)
+            .component_max(measurement.intrinsic_size.overflow(bounds.size));
+        node.with_overflow(overflow)
+            .with_fingerprint(self.fingerprint())
     }
 
-    fn draw(&self, display: &mut Display, origin: Point, size: Size) -> Result<(), Display::Error> {
-        let box_origin = Point::new(
-            origin.x + self.margin.left as i32,
-            origin.y + self.margin.top as i32,
-        );
+    fn hash_appearance(&self, mut state: &mut dyn Hasher) {
+        self.focused.hash(&mut state);
+    }
 
-        // outer half of the border
-        let outer_border = Insets::all(self.border_width() / 2);
+    fn focusable(&self) -> bool {
+        self.focusable
+    }
 
-        let box_size = size.inset(self.margin).inset(outer_border);
-        self.draw_self(display, box_origin, box_size)?;
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
 
-        // inner half of the border (the inner half gets the remainder
-        // when the border width isn't divisble by 2)
-        let inner_border_width = self.border_width() / 2 + self.border_width() % 2;
-        let inner_border = Insets::all(inner_border_width);
+    fn focused(&self) -> bool {
+        self.focused
+    }
 
-        let content_origin = Point::new(
-            box_origin.x + inner_border_width as i32 + self.padding.left as i32,
-            box_origin.y + inner_border_width as i32 + self.padding.top as i32,
-        );
-        let content_size = box_size.inset(inner_border).inset(self.padding);
-        self.draw_children(display, content_origin, content_size)
+    fn child_mut(&mut self, index: usize) -> Option<&mut (dyn Widget<Display> + 'static)> {
+        self.children.get_mut(index).map(|child| child.as_mut())
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn draw_clipped(
+        &self,
+        display: &mut Display,
+        layout: &LayoutNode,
+        clip: Rectangle,
+    ) -> Result<(), Display::Error> {
+        self.draw_self(display, self.box_bounds(layout.bounds), clip)?;
+
+        for (child, child_layout) in self.children.iter().zip(&layout.children) {
+            child.draw_clipped(display, child_layout, clip)?;
+        }
+
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::widget::{image::NinePatch, AspectRatio};
+    use alloc::rc::Rc;
+    use core::cell::Cell;
+    use embedded_graphics::{image::ImageRaw, pixelcolor::BinaryColor};
     use embedded_graphics::{mock_display::MockDisplay, pixelcolor::Rgb888};
 
+    /// Counts how many times it is measured.
+    struct Probe(Rc<Cell<u32>>);
+
+    impl<Display> Widget<Display> for Probe
+    where
+        Display: DrawTarget,
+    {
+        fn intrinsic_size(&self) -> IntrinsicSize {
+            Size::new(10, 10).into()
+        }
+
+        fn measure(&self) -> Measurement {
+            self.0.set(self.0.get() + 1);
+            Measurement::new(Size::new(10, 10).into())
+        }
+
+        fn draw_clipped(
+            &self,
+            _display: &mut Display,
+            _layout: &LayoutNode,
+            _clip: Rectangle,
+        ) -> Result<(), Display::Error> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn focus_border() {
+        let mut container = Container::new().focus_border(Border {
+            color: BinaryColor::On,
+            width: 1,
+        });
+
+        let mut display = MockDisplay::new();
+        container
+            .draw(&mut display, Point::zero(), Size::new(3, 3))
+            .unwrap();
+        display.assert_pattern(&[]);
+
+        Widget::<MockDisplay<BinaryColor>>::set_focused(&mut container, true);
+        let mut display = MockDisplay::new();
+        container
+            .draw(&mut display, Point::zero(), Size::new(3, 3))
+            .unwrap();
+        display.assert_pattern(&["###", "# #", "###"]);
+    }
+
     #[test]
     fn child_bigger_than_self() {
         let mut display: MockDisplay<Rgb888> = MockDisplay::new();
         let size = display.size();
 
         let child = Container::new()
-            .width(Some(size.width + 10))
-            .height(Some(size.height + 10));
+            .width(size.width + 10)
+            .height(size.height + 10);
         let container = Container::new().children(vec![child.boxed()]);
 
         container.draw(&mut display, Point::zero(), size).unwrap()
     }
+
+    #[test]
+    fn layout() {
+        let child = || {
+            Container::<MockDisplay<Rgb888>>::new()
+                .width(10)
+                .height(20)
+                .boxed()
+        };
+        let container = Container::new()
+            .alignment(Alignment::Center)
+            .axis(Axis::Horizontal)
+            .justification(Justification::SpaceBetween)
+            .padding(Insets::all(2))
+            .children(vec![child(), child()]);
+
+        let layout = container.layout(Rectangle::new(Point::new(5, 5), Size::new(44, 44)));
+
+        assert_eq!(
+            layout.bounds,
+            Rectangle::new(Point::new(5, 5), Size::new(44, 44))
+        );
+        assert_eq!(
+            layout.without_fingerprints().children,
+            vec![
+                LayoutNode::new(Rectangle::new(Point::new(7, 17), Size::new(10, 20))),
+                LayoutNode::new(Rectangle::new(Point::new(37, 17), Size::new(10, 20))),
+            ]
+        );
+    }
+
+    #[test]
+    fn min_and_max_sizes() {
+        let container = Container::<MockDisplay<Rgb888>>::new()
+            .axis(Axis::Horizontal)
+            .children(vec![
+                Container::new()
+                    .layout_options(LayoutOptions::new().grow(1).max_height(10))
+                    .max_width(20)
+                    .boxed(),
+                Container::new()
+                    .layout_options(LayoutOptions::new().grow(1).min_height(60))
+                    .boxed(),
+            ]);
+
+        let layout = container.layout(Rectangle::new(Point::zero(), Size::new(64, 64)));
+
+        assert_eq!(
+            layout.without_fingerprints().children,
+            vec![
+                LayoutNode::new(Rectangle::new(Point::zero(), Size::new(20, 10))),
+                LayoutNode::new(Rectangle::new(Point::new(20, 0), Size::new(44, 64))),
+            ]
+        );
+    }
+
+    #[test]
+    fn min_and_max_sizes_without_grow() {
+        let container = Container::<MockDisplay<Rgb888>>::new()
+            .axis(Axis::Horizontal)
+            .children(vec![
+                Container::new()
+                    .layout_options(LayoutOptions::new().min_width(15))
+                    .boxed(),
+                Container::new()
+                    .layout_options(LayoutOptions::new().max_width(20).shrink(0))
+                    .width(40)
+                    .boxed(),
+            ]);
+
+        let layout = container.layout(Rectangle::new(Point::zero(), Size::new(64, 64)));
+
+        assert_eq!(
+            layout.without_fingerprints().children,
+            vec![
+                LayoutNode::new(Rectangle::new(Point::zero(), Size::new(15, 64))),
+                LayoutNode::new(Rectangle::new(Point::new(15, 0), Size::new(20, 64))),
+            ]
+        );
+    }
+
+    #[test]
+    fn shrink() {
+        let child = |width, shrink| {
+            Container::<MockDisplay<Rgb888>>::new()
+                .layout_options(LayoutOptions::new().shrink(shrink))
+                .width(width)
+                .boxed()
+        };
+        let container = Container::new().axis(Axis::Horizontal).children(vec![
+            child(40, 1),
+            child(20, 1),
+            child(10, 0),
+        ]);
+
+        let layout = container.layout(Rectangle::new(Point::zero(), Size::new(40, 10)));
+
+        assert_eq!(
+            layout.without_fingerprints().children,
+            vec![
+                LayoutNode::new(Rectangle::new(Point::zero(), Size::new(20, 10)))
+                    .with_overflow(Size::new(20, 0)),
+                LayoutNode::new(Rectangle::new(Point::new(20, 0), Size::new(10, 10)))
+                    .with_overflow(Size::new(10, 0)),
+                LayoutNode::new(Rectangle::new(Point::new(30, 0), Size::new(10, 10))),
+            ]
+        );
+    }
+
+    #[test]
+    fn wrap() {
+        let child = || {
+            Container::<MockDisplay<Rgb888>>::new()
+                .width(20)
+                .height(10)
+                .boxed()
+        };
+        let container = Container::new()
+            .axis(Axis::Horizontal)
+            .justification(Justification::Center)
+            .line_alignment(LineAlignment::End)
+            .wrap(true)
+            .children(vec![child(), child(), child()]);
+
+        let layout = container.layout(Rectangle::new(Point::zero(), Size::new(50, 30)));
+
+        assert_eq!(
+            layout.without_fingerprints().children,
+            vec![
+                LayoutNode::new(Rectangle::new(Point::new(5, 10), Size::new(20, 10))),
+                LayoutNode::new(Rectangle::new(Point::new(25, 10), Size::new(20, 10))),
+                LayoutNode::new(Rectangle::new(Point::new(15, 20), Size::new(20, 10))),
+            ]
+        );
+    }
+
+    #[test]
+    fn gap() {
+        let child = |grow| {
+            Container::<MockDisplay<Rgb888>>::new()
+                .layout_options(LayoutOptions::new().grow(grow))
+                .width(10)
+                .height(10)
+                .boxed()
+        };
+        let container = Container::new()
+            .axis(Axis::Horizontal)
+            .alignment(Alignment::Start)
+            .column_gap(5)
+            .row_gap(2)
+            .wrap(true)
+            .children(vec![child(0), child(1), child(0), child(0)]);
+
+        assert_eq!(
+            container.intrinsic_size(),
+            IntrinsicSize::new(Some(55), Some(10))
+        );
+
+        let layout = container.layout(Rectangle::new(Point::zero(), Size::new(35, 30)));
+
+        assert_eq!(
+            layout.without_fingerprints().children,
+            vec![
+                LayoutNode::new(Rectangle::new(Point::new(0, 0), Size::new(10, 10))),
+                LayoutNode::new(Rectangle::new(Point::new(15, 0), Size::new(20, 10))),
+                LayoutNode::new(Rectangle::new(Point::new(0, 12), Size::new(10, 10))),
+                LayoutNode::new(Rectangle::new(Point::new(15, 12), Size::new(10, 10))),
+            ]
+        );
+    }
+
+    #[test]
+    fn positioning() {
+        let container = Container::<MockDisplay<Rgb888>>::new()
+            .axis(Axis::Horizontal)
+            .alignment(Alignment::Start)
+            .padding(Insets::all(2))
+            .children(vec![
+                Container::new().width(10).height(10).boxed(),
+                Container::new()
+                    .layout_options(
+                        LayoutOptions::new()
+                            .position(Position::Absolute)
+                            .top(1)
+                            .right(1),
+                    )
+                    .width(5)
+                    .height(5)
+                    .boxed(),
+                Container::new()
+                    .layout_options(
+                        LayoutOptions::new()
+                            .position(Position::Relative)
+                            .left(3)
+                            .bottom(1),
+                    )
+                    .width(10)
+                    .height(10)
+                    .boxed(),
+            ]);
+
+        assert_eq!(
+            container.intrinsic_size(),
+            IntrinsicSize::new(Some(24), Some(14))
+        );
+
+        let layout = container.layout(Rectangle::new(Point::zero(), Size::new(40, 20)));
+
+        assert_eq!(
+            layout.without_fingerprints().children,
+            vec![
+                LayoutNode::new(Rectangle::new(Point::new(2, 2), Size::new(10, 10))),
+                LayoutNode::new(Rectangle::new(Point::new(32, 3), Size::new(5, 5))),
+                LayoutNode::new(Rectangle::new(Point::new(15, 1), Size::new(10, 10))),
+            ]
+        );
+    }
+
+    #[test]
+    fn measures_nested_children_once() {
+        let measures = Rc::new(Cell::new(0));
+        let mut widget: Box<dyn Widget<MockDisplay<Rgb888>>> = Probe(measures.clone()).boxed();
+
+        for _ in 0..8 {
+            widget = Container::new().children(vec![widget]).boxed();
+        }
+
+        widget.layout(Rectangle::new(Point::zero(), Size::new(20, 20)));
+        assert_eq!(measures.get(), 1);
+    }
+
+    #[test]
+    fn extreme_offsets_and_insets() {
+        let container = Container::<MockDisplay<Rgb888>>::new().children(vec![
+            Container::new()
+                .layout_options(
+                    LayoutOptions::new()
+                        .position(Position::Absolute)
+                        .left(i32::MIN),
+                )
+                .boxed(),
+            Container::new()
+                .layout_options(
+                    LayoutOptions::new()
+                        .position(Position::Relative)
+                        .right(i32::MIN),
+                )
+                .boxed(),
+        ]);
+
+        let layout = container.layout(Rectangle::new(Point::zero(), Size::new(20, 20)));
+        assert_eq!(
+            layout.children[0].bounds,
+            Rectangle::new(Point::new(i32::MIN, 0), Size::new(i32::MAX as u32, 20))
+        );
+        assert_eq!(layout.children[1].bounds.top_left, Point::new(i32::MAX, 0));
+
+        let container = Container::<MockDisplay<Rgb888>>::new()
+            .margin(Insets::all(u32::MAX))
+            .children(vec![Container::new().boxed()]);
+
+        let layout = container.layout(Rectangle::new(Point::zero(), Size::new(20, 20)));
+        assert_eq!(
+            layout.children[0].bounds.top_left,
+            Point::new(i32::MAX, i32::MAX)
+        );
+    }
+
+    #[test]
+    fn insets_bigger_than_self() {
+        let mut display: MockDisplay<Rgb888> = MockDisplay::new();
+
+        let child = Container::new().width(10).height(10);
+        let container = Container::new()
+            .border(Border {
+                color: Rgb888::RED,
+                width: 3,
+            })
+            .margin(Insets::all(4))
+            .padding(Insets::horizontal(10))
+            .children(vec![child.boxed()]);
+
+        let layout = container.layout(Rectangle::new(Point::zero(), Size::new(20, 20)));
+        container.draw_layout(&mut display, &layout).unwrap();
+
+        let overflows = layout.overflows();
+        assert_eq!(overflows.len(), 2);
+        assert_eq!(overflows[0].path, vec![]);
+        assert_eq!(overflows[0].amount, Size::new(24, 4));
+        assert_eq!(overflows[1].path, vec![0]);
+        assert_eq!(overflows[1].amount, Size::new(10, 4));
+    }
+
+    #[test]
+    fn percentages() {
+        let container = Container::<MockDisplay<Rgb888>>::new()
+            .axis(Axis::Horizontal)
+            .padding(Insets::horizontal(Length::Percent(10)))
+            .children(vec![
+                Container::new()
+                    .width(Length::Percent(25))
+                    .height(Length::Percent(50))
+                    .boxed(),
+                Container::new().width(10).height(10).boxed(),
+            ]);
+
+        assert_eq!(
+            container.intrinsic_size(),
+            IntrinsicSize::new(Some(10), Some(10))
+        );
+
+        let layout = container.layout(Rectangle::new(Point::zero(), Size::new(50, 20)));
+
+        assert_eq!(
+            layout.without_fingerprints().children,
+            vec![
+                LayoutNode::new(Rectangle::new(Point::new(5, 0), Size::new(10, 10))),
+                LayoutNode::new(Rectangle::new(Point::new(15, 0), Size::new(10, 10))),
+            ]
+        );
+    }
+
+    #[test]
+    fn aspect_ratio() {
+        let container = Container::<MockDisplay<Rgb888>>::new()
+            .alignment(Alignment::Stretch)
+            .children(vec![
+                Container::new()
+                    .layout_options(LayoutOptions::new().aspect_ratio(AspectRatio::new(2, 1)))
+                    .boxed(),
+                Container::new()
+                    .width(10)
+                    .layout_options(LayoutOptions::new().aspect_ratio(AspectRatio::new(1, 2)))
+                    .boxed(),
+                Container::new()
+                    .layout_options(
+                        LayoutOptions::new()
+                            .alignment(Alignment::Start)
+                            .aspect_ratio(AspectRatio::square())
+                            .grow(1),
+                    )
+                    .boxed(),
+            ]);
+
+        let layout = container.layout(Rectangle::new(Point::zero(), Size::new(40, 70)));
+
+        assert_eq!(
+            layout.without_fingerprints().children,
+            vec![
+                LayoutNode::new(Rectangle::new(Point::zero(), Size::new(40, 20))),
+                LayoutNode::new(Rectangle::new(Point::new(0, 20), Size::new(10, 20))),
+                LayoutNode::new(Rectangle::new(Point::new(0, 40), Size::new(30, 30))),
+            ]
+        );
+    }
+
+    #[test]
+    fn background() {
+        let mut display: MockDisplay<BinaryColor> = MockDisplay::new();
+
+        // #..#
+        // .#..
+        // #..#
+        let raw: &'static _ = Box::leak(Box::new(ImageRaw::<BinaryColor>::new(
+            &[0b1001_0000, 0b0100_0000, 0b1001_0000],
+            4,
+        )));
+        let container = Container::new()
+            .margin(Insets::all(1))
+            .background(NinePatch::new(raw, Insets::all(1)).boxed());
+
+        container
+            .draw(&mut display, Point::zero(), Size::new(8, 5))
+            .unwrap();
+        display.assert_pattern(&["       ", " #....#", " .##...", " #....#"]);
+    }
 }
@@ -0,0 +1,73 @@
+use super::KeyCode;
+use embedded_graphics::primitives::Rectangle;
+
+/// A direction to move the focus in.
+#[derive(Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub fn from_key_code(key_code: KeyCode) -> Option<Self> {
+        match key_code {
+            KeyCode::Up => Some(Direction::Up),
+            KeyCode::Down => Some(Direction::Down),
+            KeyCode::Left => Some(Direction::Left),
+            KeyCode::Right => Some(Direction::Right),
+            KeyCode::Select | KeyCode::Back | KeyCode::Other(_) => None,
+        }
+    }
+
+    /// The index of the nearest of `candidates` whose center is beyond the
+    /// center of `from` in this direction. Candidates that are out of line
+    /// with `from` count as further away.
+    pub fn nearest(&self, from: Rectangle, candidates: &[Rectangle]) -> Option<usize> {
+        let from = from.center();
+
+        candidates
+            .iter()
+            .enumerate()
+            .filter_map(|(index, candidate)| {
+                let offset = candidate.center() - from;
+                let (distance, misalignment) = match self {
+                    Direction::Up => (-offset.y, offset.x),
+                    Direction::Down => (offset.y, offset.x),
+                    Direction::Left => (-offset.x, offset.y),
+                    Direction::Right => (offset.x, offset.y),
+                };
+
+                (distance > 0).then(|| {
+                    let score = distance as i64 + 2 * (misalignment as i64).abs();
+                    (score, index)
+                })
+            })
+            .min()
+            .map(|(_, index)| index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::prelude::*;
+
+    #[test]
+    fn nearest() {
+        let square = |x, y| Rectangle::new(Point::new(x, y), Size::new(10, 10));
+        // a row of three squares, with one below the first
+        let candidates = [square(0, 0), square(20, 0), square(40, 0), square(0, 20)];
+
+        assert_eq!(
+            Direction::Right.nearest(candidates[0], &candidates),
+            Some(1)
+        );
+        assert_eq!(Direction::Left.nearest(candidates[2], &candidates), Some(1));
+        assert_eq!(Direction::Down.nearest(candidates[1], &candidates), Some(3));
+        assert_eq!(Direction::Up.nearest(candidates[3], &candidates), Some(0));
+        assert_eq!(Direction::Up.nearest(candidates[0], &candidates), None);
+    }
+}
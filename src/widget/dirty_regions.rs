@@ -0,0 +1,80 @@
+use alloc::vec::Vec;
+use embedded_graphics::primitives::Rectangle;
+
+/// The rectangles of a display that need to be redrawn. Overlapping
+/// rectangles are merged, so that no pixel is redrawn twice.
+#[derive(Clone, Default, Eq, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub struct DirtyRegions {
+    rectangles: Vec<Rectangle>,
+}
+
+impl DirtyRegions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds `rectangle`, merging it with the rectangles it overlaps.
+    pub fn add(&mut self, mut rectangle: Rectangle) {
+        if rectangle.is_zero_sized() {
+            return;
+        }
+
+        while let Some(index) = self
+            .rectangles
+            .iter()
+            .position(|other| !other.intersection(&rectangle).is_zero_sized())
+        {
+            rectangle = envelope(&rectangle, &self.rectangles.swap_remove(index));
+        }
+
+        self.rectangles.push(rectangle);
+    }
+
+    pub fn rectangles(&self) -> &[Rectangle] {
+        &self.rectangles
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rectangles.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.rectangles.clear();
+    }
+}
+
+/// The smallest rectangle containing both `a` and `b`, which are not empty.
+fn envelope(a: &Rectangle, b: &Rectangle) -> Rectangle {
+    let (a_bottom_right, b_bottom_right) = match (a.bottom_right(), b.bottom_right()) {
+        (Some(a_bottom_right), Some(b_bottom_right)) => (a_bottom_right, b_bottom_right),
+        _ => return *a,
+    };
+
+    Rectangle::with_corners(
+        a.top_left.component_min(b.top_left),
+        a_bottom_right.component_max(b_bottom_right),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::prelude::*;
+
+    #[test]
+    fn add() {
+        let mut regions = DirtyRegions::new();
+        regions.add(Rectangle::new(Point::zero(), Size::new(4, 4)));
+        regions.add(Rectangle::new(Point::new(10, 10), Size::new(2, 2)));
+        regions.add(Rectangle::new(Point::new(6, 0), Size::zero()));
+        assert_eq!(regions.rectangles().len(), 2);
+
+        // overlaps both, so all three are merged
+        regions.add(Rectangle::new(Point::new(2, 2), Size::new(9, 9)));
+        assert_eq!(
+            regions.rectangles(),
+            &[Rectangle::new(Point::zero(), Size::new(12, 12))]
+        );
+    }
+}
@@ -0,0 +1,35 @@
+use super::KeyCode;
+use alloc::boxed::Box;
+use core::any::Any;
+use embedded_graphics::prelude::*;
+
+/// A message for the application, produced by a widget in response to an
+/// event. The application downcasts it to its own message type.
+pub type Message = Box<dyn Any>;
+
+/// An input event, which is sent through the widget tree with
+/// `Widget::event`.
+#[derive(Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub enum Event {
+    TouchDown(Point),
+    TouchMove(Point),
+    TouchUp(Point),
+    /// A button was pressed.
+    Key(KeyCode),
+    /// A rotary encoder was turned by a number of steps, which are positive
+    /// when it is turned clockwise.
+    Encoder(i32),
+}
+
+impl Event {
+    /// The point that was touched, for touch events.
+    pub fn touch_point(&self) -> Option<Point> {
+        match *self {
+            Event::TouchDown(point) | Event::TouchMove(point) | Event::TouchUp(point) => {
+                Some(point)
+            }
+            Event::Key(_) | Event::Encoder(_) => None,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use core::hash::Hasher;
+
+/// A 64-bit FNV-1a hasher, used to tell when a widget's appearance changes.
+/// It isn't randomly seeded, so the same appearance always has the same
+/// fingerprint.
+pub(crate) struct Fingerprint(u64);
+
+impl Fingerprint {
+    pub fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for Fingerprint {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use crate::widget::{container::Alignment, Widget};
+use alloc::boxed::Box;
+use cherry_macros::Builder;
+use embedded_graphics::prelude::*;
+
+/// A child of a `Grid`, along with the rows and columns it occupies.
+#[derive(Builder)]
+pub struct Cell<Display>
+where
+    Display: DrawTarget,
+{
+    #[omit]
+    pub(super) child: Box<dyn Widget<Display>>,
+    pub(super) column: usize,
+    pub(super) column_span: usize,
+    pub(super) horizontal_alignment: Option<Alignment>,
+    pub(super) row: usize,
+    pub(super) row_span: usize,
+    pub(super) vertical_alignment: Option<Alignment>,
+}
+
+impl<Display> Cell<Display>
+where
+    Display: DrawTarget,
+{
+    pub fn new(row: usize, column: usize, child: Box<dyn Widget<Display>>) -> Self {
+        Self {
+            child,
+            column,
+            column_span: 1,
+            horizontal_alignment: Default::default(),
+            row,
+            row_span: 1,
+            vertical_alignment: Default::default(),
+        }
+    }
+}
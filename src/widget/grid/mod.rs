@@ -0,0 +1,316 @@
+mod cell;
+mod track;
+
+pub use cell::Cell;
+pub use track::Track;
+
+use super::{
+    axis_size::AxisSize,
+    container::{Alignment, Axis},
+    offset::Offset,
+    IntrinsicSize, LayoutNode, LayoutOptions, Measurement, Widget,
+};
+use alloc::vec::Vec;
+use cherry_macros::Builder;
+use core::any::Any;
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+use track::Span;
+
+/// Places its children into rows and columns. Rows and columns without an
+/// explicit `Track` (because a child is placed beyond them) are `Track::Auto`.
+///
+/// Unlike in a `Container`, `Alignment::Stretch` fills the whole cell, even
+/// when the child has an intrinsic size.
+#[derive(Builder)]
+pub struct Grid<Display>
+where
+    Display: DrawTarget,
+{
+    cells: Vec<Cell<Display>>,
+    column_gap: u32,
+    columns: Vec<Track>,
+    horizontal_alignment: Alignment,
+    layout_options: LayoutOptions,
+    row_gap: u32,
+    rows: Vec<Track>,
+    vertical_alignment: Alignment,
+}
+
+impl<Display> Default for Grid<Display>
+where
+    Display: DrawTarget,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Display> Grid<Display>
+where
+    Display: DrawTarget,
+{
+    pub fn new() -> Self {
+        Self {
+            cells: Default::default(),
+            column_gap: Default::default(),
+            columns: Default::default(),
+            horizontal_alignment: Default::default(),
+            layout_options: Default::default(),
+            row_gap: Default::default(),
+            rows: Default::default(),
+            vertical_alignment: Default::default(),
+        }
+    }
+
+    /// Sets both the column gap and the row gap.
+    pub fn gap(self, gap: u32) -> Self {
+        self.column_gap(gap).row_gap(gap)
+    }
+
+    fn gap_for_axis(&self, axis: Axis) -> u32 {
+        match axis {
+            Axis::Horizontal => self.column_gap,
+            Axis::Vertical => self.row_gap,
+        }
+    }
+
+    /// The explicit tracks for `axis`, followed by as many auto tracks as are
+    /// needed to contain every cell.
+    fn tracks(&self, axis: Axis) -> Vec<Track> {
+        let explicit = match axis {
+            Axis::Horizontal => &self.columns,
+            Axis::Vertical => &self.rows,
+        };
+        let count = self
+            .cells
+            .iter()
+            .map(|cell| {
+                let (start, len) = Self::cell_span(cell, axis);
+                start + len
+            })
+            .max()
+            .unwrap_or(0)
+            .max(explicit.len());
+
+        let mut tracks = explicit.clone();
+        tracks.resize(count, Track::Auto);
+        tracks
+    }
+
+    fn cell_span(cell: &Cell<Display>, axis: Axis) -> (usize, usize) {
+        match axis {
+            Axis::Horizontal => (cell.column, cell.column_span.max(1)),
+            Axis::Vertical => (cell.row, cell.row_span.max(1)),
+        }
+    }
+
+    /// The content sizes of the tracks for `axis`, given the measurements of
+    /// the cells' children.
+    fn content_sizes(&self, tracks: &[Track], children: &[Measurement], axis: Axis) -> Vec<u32> {
+        let spans: Vec<Span> = self
+            .cells
+            .iter()
+            .zip(children)
+            .map(|(cell, measurement)| {
+                let (start, len) = Self::cell_span(cell, axis);
+                let size = cell
+                    .child
+                    .layout_options()
+                    .resolve_intrinsic_size(measurement.intrinsic_size, None)
+                    .for_axis(axis);
+
+                Span { start, len, size }
+            })
+            .collect();
+
+        track::content_sizes(tracks, &spans, self.gap_for_axis(axis))
+    }
+
+    /// Resolves the offset and size of each track along `axis`.
+    fn resolve_tracks(
+        &self,
+        children: &[Measurement],
+        available: u32,
+        axis: Axis,
+    ) -> (Vec<u32>, Vec<u32>) {
+        let tracks = self.tracks(axis);
+        let content_sizes = self.content_sizes(&tracks, children, axis);
+        let sizes = track::resolve(&tracks, &content_sizes, available, self.gap_for_axis(axis));
+        let offsets = track::offsets(&sizes, self.gap_for_axis(axis));
+
+        (offsets, sizes)
+    }
+}
+
+impl<Display> Widget<Display> for Grid<Display>
+where
+    Display: DrawTarget,
+{
+    fn intrinsic_size(&self) -> IntrinsicSize {
+        self.measure().intrinsic_size
+    }
+
+    fn layout_options(&self) -> LayoutOptions {
+        self.layout_options
+    }
+
+    fn measure(&self) -> Measurement {
+        self.measure_with_children(self.cells.iter().map(|cell| cell.child.measure()).collect())
+    }
+
+    fn measure_with_children(&self, children: Vec<Measurement>) -> Measurement {
+        let width = track::total_size(
+            &self.content_sizes(&self.tracks(Axis::Horizontal), &children, Axis::Horizontal),
+            self.column_gap,
+        );
+        let height = track::total_size(
+            &self.content_sizes(&self.tracks(Axis::Vertical), &children, Axis::Vertical),
+            self.row_gap,
+        );
+
+        let intrinsic_size = self
+            .layout_options
+            .clamp_intrinsic_size(Size::new(width, height).into());
+
+        Measurement::with_children(intrinsic_size, children)
+    }
+
+    fn arrange(&self, measurement: &Measurement, bounds: Rectangle) -> LayoutNode {
+        let children = &measurement.children;
+        let (column_offsets, column_sizes) =
+            self.resolve_tracks(children, bounds.size.width, Axis::Horizontal);
+        let (row_offsets, row_sizes) =
+            self.resolve_tracks(children, bounds.size.height, Axis::Vertical);
+
+        let cell_bounds = |start: usize, len: usize, offsets: &[u32], sizes: &[u32], gap: u32| {
+            let size = track::total_size(&sizes[start..start + len], gap);
+            (offsets[start], size)
+        };
+
+        let nodes = self
+            .cells
+            .iter()
+            .zip(children)
+            .map(|(cell, measurement)| {
+                let layout_options = cell.child.layout_options();
+                let intrinsic_size =
+                    layout_options.resolve_intrinsic_size(measurement.intrinsic_size, None);
+
+                let (column, column_span) = Self::cell_span(cell, Axis::Horizontal);
+                let (x, cell_width) = cell_bounds(
+                    column,
+                    column_span,
+                    &column_offsets,
+                    &column_sizes,
+                    self.column_gap,
+                );
+                let (x_offset, width) = cell
+                    .horizontal_alignment
+                    .unwrap_or(self.horizontal_alignment)
+                    .fill(
+                        cell_width,
+                        intrinsic_size.width,
+                        &layout_options,
+                        Axis::Horizontal,
+                    );
+
+                let (row, row_span) = Self::cell_span(cell, Axis::Vertical);
+                let (y, cell_height) =
+                    cell_bounds(row, row_span, &row_offsets, &row_sizes, self.row_gap);
+                let (y_offset, height) = cell
+                    .vertical_alignment
+                    .unwrap_or(self.vertical_alignment)
+                    .fill(
+                        cell_height,
+                        intrinsic_size.height,
+                        &layout_options,
+                        Axis::Vertical,
+                    );
+
+                let origin = bounds
+                    .top_left
+                    .offset(x.saturating_add(x_offset), y.saturating_add(y_offset));
+                cell.child.arrange(
+                    measurement,
+                    Rectangle::new(origin, Size::new(width, height)),
+                )
+            })
+            .collect();
+
+        let node = LayoutNode::with_children(bounds, nodes);
+        let overflow = node
+            .children_overflow(bounds)
+            .component_max(measurement.intrinsic_size.overflow(bounds.size));
+        node.with_overflow(overflow)
+            .with_fingerprint(self.fingerprint())
+    }
+
+    fn child_mut(&mut self, index: usize) -> Option<&mut (dyn Widget<Display> + 'static)> {
+        self.cells.get_mut(index).map(|cell| cell.child.as_mut())
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn draw_clipped(
+        &self,
+        display: &mut Display,
+        layout: &LayoutNode,
+        clip: Rectangle,
+    ) -> Result<(), Display::Error> {
+        for (cell, child_layout) in self.cells.iter().zip(&layout.children) {
+            cell.child.draw_clipped(display, child_layout, clip)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::container::Container;
+    use alloc::boxed::Box;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::Rgb888};
+
+    fn block(width: u32, height: u32) -> Box<dyn Widget<MockDisplay<Rgb888>>> {
+        Container::new().width(width).height(height).boxed()
+    }
+
+    #[test]
+    fn layout() {
+        let grid = Grid::new()
+            .columns(vec![Track::Fixed(10), Track::Fraction(1), Track::Auto])
+            .gap(2)
+            .horizontal_alignment(Alignment::Center)
+            .cells(vec![
+                Cell::new(0, 0, block(10, 10)),
+                Cell::new(0, 1, block(4, 10)),
+                Cell::new(0, 2, block(6, 10)),
+                Cell::new(1, 0, block(20, 5))
+                    .column_span(2)
+                    .vertical_alignment(Alignment::End),
+            ]);
+
+        assert_eq!(
+            grid.intrinsic_size(),
+            IntrinsicSize::new(Some(28), Some(17))
+        );
+
+        let layout = grid.layout(Rectangle::new(Point::zero(), Size::new(40, 30)));
+
+        assert_eq!(
+            layout.without_fingerprints().children,
+            vec![
+                LayoutNode::new(Rectangle::new(Point::new(0, 0), Size::new(10, 10))),
+                LayoutNode::new(Rectangle::new(Point::new(20, 0), Size::new(4, 10))),
+                LayoutNode::new(Rectangle::new(Point::new(34, 0), Size::new(6, 10))),
+                LayoutNode::new(Rectangle::new(Point::new(6, 12), Size::new(20, 5))),
+            ]
+        );
+    }
+}
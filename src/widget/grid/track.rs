@@ -0,0 +1,198 @@
+use alloc::vec::Vec;
+
+/// The sizing of a grid row or column.
+#[derive(Clone, Copy, Default, Eq, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub enum Track {
+    /// A fixed number of pixels.
+    Fixed(u32),
+    /// A share of the space left over by the other tracks, in proportion to
+    /// the fractions of all fractional tracks (like `grow` in a `Container`).
+    Fraction(u32),
+    /// The largest intrinsic size of the children in the track.
+    #[default]
+    Auto,
+}
+
+/// The position of a child along one axis of the grid, and its intrinsic size
+/// along that axis.
+pub(super) struct Span {
+    pub start: usize,
+    pub len: usize,
+    pub size: Option<u32>,
+}
+
+fn sum(sizes: &[u32]) -> u32 {
+    sizes
+        .iter()
+        .fold(0, |total: u32, size| total.saturating_add(*size))
+}
+
+fn total_gap(count: usize, gap: u32) -> u32 {
+    gap.saturating_mul(count.max(1) as u32 - 1)
+}
+
+/// Computes the size each track needs to fit its children. Children spanning
+/// several tracks share any size they need beyond those tracks equally among
+/// the spanned tracks that aren't fixed.
+pub(super) fn content_sizes(tracks: &[Track], spans: &[Span], gap: u32) -> Vec<u32> {
+    let mut sizes: Vec<u32> = tracks
+        .iter()
+        .map(|track| match track {
+            Track::Fixed(size) => *size,
+            _ => 0,
+        })
+        .collect();
+
+    let is_flexible = |index: usize| !matches!(tracks[index], Track::Fixed(_));
+
+    for span in spans.iter().filter(|span| span.len == 1) {
+        if let Some(size) = span.size {
+            if is_flexible(span.start) {
+                sizes[span.start] = sizes[span.start].max(size);
+            }
+        }
+    }
+
+    for span in spans.iter().filter(|span| span.len > 1) {
+        let size = match span.size {
+            Some(size) => size,
+            None => continue,
+        };
+
+        let range = span.start..span.start + span.len;
+        let current = sum(&sizes[range.clone()]).saturating_add(total_gap(span.len, gap));
+        let flexible: Vec<usize> = range.filter(|index| is_flexible(*index)).collect();
+
+        if size <= current || flexible.is_empty() {
+            continue;
+        }
+
+        let deficit = size - current;
+        let count = flexible.len() as u32;
+
+        for (position, index) in flexible.into_iter().enumerate() {
+            // the first tracks get the remainder
+            let remainder = if (position as u32) < deficit % count {
+                1
+            } else {
+                0
+            };
+            sizes[index] = sizes[index].saturating_add(deficit / count + remainder);
+        }
+    }
+
+    sizes
+}
+
+/// The total size of the tracks and the gaps between them.
+pub(super) fn total_size(sizes: &[u32], gap: u32) -> u32 {
+    sum(sizes).saturating_add(total_gap(sizes.len(), gap))
+}
+
+/// Resolves the final track sizes. Fixed and auto tracks get their content
+/// sizes, and fractional tracks share whatever space is left.
+pub(super) fn resolve(
+    tracks: &[Track],
+    content_sizes: &[u32],
+    available: u32,
+    gap: u32,
+) -> Vec<u32> {
+    let fraction_total: u64 = tracks
+        .iter()
+        .map(|track| match track {
+            Track::Fraction(fraction) => *fraction as u64,
+            _ => 0,
+        })
+        .sum();
+
+    let used = tracks
+        .iter()
+        .zip(content_sizes)
+        .filter(|(track, _)| !matches!(track, Track::Fraction(_)))
+        .fold(0, |total: u32, (_, size)| total.saturating_add(*size))
+        .saturating_add(total_gap(tracks.len(), gap));
+    let remaining = available.saturating_sub(used) as u64;
+
+    // the fractional sizes are computed from the running total of the
+    // fractions, so that rounding never leaves part of the space unassigned
+    // (fractional tracks get nothing when every fraction is zero)
+    let mut cumulative_fraction: u64 = 0;
+    let mut assigned = 0;
+
+    tracks
+        .iter()
+        .zip(content_sizes)
+        .map(|(track, size)| match track {
+            Track::Fraction(fraction) => {
+                cumulative_fraction += *fraction as u64;
+                let total = (remaining * cumulative_fraction)
+                    .checked_div(fraction_total)
+                    .unwrap_or(0) as u32;
+                let size = total - assigned;
+                assigned = total;
+                size
+            }
+            _ => *size,
+        })
+        .collect()
+}
+
+/// The offset of the start of each track.
+pub(super) fn offsets(sizes: &[u32], gap: u32) -> Vec<u32> {
+    let mut offset: u32 = 0;
+
+    sizes
+        .iter()
+        .map(|size| {
+            let start = offset;
+            offset = offset.saturating_add(*size).saturating_add(gap);
+            start
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(start: usize, len: usize, size: u32) -> Span {
+        Span {
+            start,
+            len,
+            size: Some(size),
+        }
+    }
+
+    #[test]
+    fn content_sizes_of_tracks() {
+        let tracks = [Track::Fixed(10), Track::Auto, Track::Fraction(1)];
+        let spans = [span(0, 1, 30), span(1, 1, 20), span(2, 1, 5)];
+        assert_eq!(content_sizes(&tracks, &spans, 2), vec![10, 20, 5]);
+    }
+
+    #[test]
+    fn content_sizes_with_spanning_child() {
+        let tracks = [Track::Fixed(10), Track::Auto, Track::Auto];
+        let spans = [span(1, 1, 10), span(0, 3, 37)];
+        assert_eq!(content_sizes(&tracks, &spans, 2), vec![10, 17, 6]);
+    }
+
+    #[test]
+    fn resolve_fractions() {
+        let tracks = [Track::Fixed(10), Track::Fraction(1), Track::Fraction(2)];
+        assert_eq!(resolve(&tracks, &[10, 0, 0], 50, 2), vec![10, 12, 24]);
+        assert_eq!(resolve(&tracks, &[10, 0, 0], 5, 2), vec![10, 0, 0]);
+    }
+
+    #[test]
+    fn resolve_zero_fractions() {
+        let tracks = [Track::Fixed(10), Track::Fraction(0), Track::Fraction(0)];
+        assert_eq!(resolve(&tracks, &[10, 0, 0], 50, 2), vec![10, 0, 0]);
+    }
+
+    #[test]
+    fn offsets_of_tracks() {
+        assert_eq!(offsets(&[10, 20, 5], 2), vec![0, 12, 34]);
+    }
+}
@@ -0,0 +1,26 @@
+use super::ColorMap;
+use embedded_graphics::{
+    pixelcolor::{BinaryColor, Gray8},
+    prelude::*,
+};
+
+// the thresholds of a 4x4 Bayer matrix, in sixteenths
+const BAYER: [[u8; 4]; 4] = [[0, 8, 2, 10], [12, 4, 14, 6], [3, 11, 1, 9], [15, 7, 13, 5]];
+
+/// Converts colors to `BinaryColor` with ordered dithering, so that shades of
+/// gray become patterns of pixels that are on and off.
+#[derive(Clone, Copy, Default)]
+pub struct Dither;
+
+impl<Source> ColorMap<Source, BinaryColor> for Dither
+where
+    Source: Into<Gray8>,
+{
+    fn map(&self, point: Point, color: Source) -> Option<BinaryColor> {
+        let threshold = BAYER[point.y.rem_euclid(4) as usize][point.x.rem_euclid(4) as usize];
+
+        // on if luma / 255 > (threshold + 0.5) / 16
+        let luma = color.into().luma() as u32;
+        Some((luma * 32 > (threshold as u32 * 2 + 1) * 255).into())
+    }
+}
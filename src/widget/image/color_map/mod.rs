@@ -0,0 +1,40 @@
+mod dither;
+mod threshold;
+mod tint;
+
+pub use dither::Dither;
+pub use threshold::Threshold;
+pub use tint::Tint;
+
+use embedded_graphics::prelude::*;
+
+/// Maps the colors of an image to the colors of a display. Maps are given the
+/// position of each pixel on the display, so that they can dither.
+pub trait ColorMap<Source, Target> {
+    /// Returns `None` for pixels that shouldn't be drawn.
+    fn map(&self, point: Point, color: Source) -> Option<Target>;
+}
+
+/// Draws the colors of an image as they are, which requires the display to
+/// use the same color type as the image.
+#[derive(Clone, Copy, Default)]
+pub struct SameColor;
+
+impl<Color> ColorMap<Color, Color> for SameColor {
+    fn map(&self, _point: Point, color: Color) -> Option<Color> {
+        Some(color)
+    }
+}
+
+/// Converts colors with a function, such as `|color: Rgb888| Rgb565::from(color)`.
+#[derive(Clone, Copy)]
+pub struct Convert<F>(pub F);
+
+impl<F, Source, Target> ColorMap<Source, Target> for Convert<F>
+where
+    F: Fn(Source) -> Target,
+{
+    fn map(&self, _point: Point, color: Source) -> Option<Target> {
+        Some((self.0)(color))
+    }
+}
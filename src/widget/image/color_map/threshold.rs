@@ -0,0 +1,33 @@
+use super::ColorMap;
+use embedded_graphics::{
+    pixelcolor::{BinaryColor, Gray8},
+    prelude::*,
+};
+
+/// Converts colors to `BinaryColor`, where colors with a luma of at least
+/// `level` are on.
+#[derive(Clone, Copy)]
+pub struct Threshold {
+    pub level: u8,
+}
+
+impl Threshold {
+    pub fn new(level: u8) -> Self {
+        Self { level }
+    }
+}
+
+impl Default for Threshold {
+    fn default() -> Self {
+        Self::new(128)
+    }
+}
+
+impl<Source> ColorMap<Source, BinaryColor> for Threshold
+where
+    Source: Into<Gray8>,
+{
+    fn map(&self, _point: Point, color: Source) -> Option<BinaryColor> {
+        Some((color.into().luma() >= self.level).into())
+    }
+}
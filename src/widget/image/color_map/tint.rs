@@ -0,0 +1,27 @@
+use super::ColorMap;
+use embedded_graphics::{pixelcolor::BinaryColor, prelude::*};
+
+/// Draws a 1-bit mask in `color`. Pixels that are off aren't drawn, so the
+/// background shows through.
+#[derive(Clone, Copy)]
+pub struct Tint<Color> {
+    pub color: Color,
+}
+
+impl<Color> Tint<Color> {
+    pub fn new(color: Color) -> Self {
+        Self { color }
+    }
+}
+
+impl<Color> ColorMap<BinaryColor, Color> for Tint<Color>
+where
+    Color: Copy,
+{
+    fn map(&self, _point: Point, color: BinaryColor) -> Option<Color> {
+        match color {
+            BinaryColor::On => Some(self.color),
+            BinaryColor::Off => None,
+        }
+    }
+}
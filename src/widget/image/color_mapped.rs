@@ -0,0 +1,52 @@
+use super::ColorMap;
+use core::marker::PhantomData;
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+/// A draw target in the colors of an image, which maps each pixel to the
+/// colors of `display`.
+pub(super) struct ColorMapped<'a, Display, Map, Color> {
+    display: &'a mut Display,
+    color_map: &'a Map,
+    color: PhantomData<Color>,
+}
+
+impl<'a, Display, Map, Color> ColorMapped<'a, Display, Map, Color> {
+    pub fn new(display: &'a mut Display, color_map: &'a Map) -> Self {
+        Self {
+            display,
+            color_map,
+            color: PhantomData,
+        }
+    }
+}
+
+impl<Display, Map, Color> Dimensions for ColorMapped<'_, Display, Map, Color>
+where
+    Display: Dimensions,
+{
+    fn bounding_box(&self) -> Rectangle {
+        self.display.bounding_box()
+    }
+}
+
+impl<Display, Map, Color> DrawTarget for ColorMapped<'_, Display, Map, Color>
+where
+    Display: DrawTarget,
+    Map: ColorMap<Color, Display::Color>,
+    Color: PixelColor,
+{
+    type Color = Color;
+    type Error = Display::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let color_map = self.color_map;
+
+        self.display
+            .draw_iter(pixels.into_iter().filter_map(|Pixel(point, color)| {
+                color_map.map(point, color).map(|color| Pixel(point, color))
+            }))
+    }
+}
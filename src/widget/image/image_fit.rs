@@ -0,0 +1,20 @@
+/// How an image is sized and placed in its bounds.
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub enum ImageFit {
+    /// Draws the image at its size (multiplied by its scale) in the top-left
+    /// corner of its bounds.
+    #[default]
+    None,
+    /// Scales the image to the largest size that fits in its bounds, keeping
+    /// its aspect ratio, and centers it.
+    Contain,
+    /// Scales the image to the smallest size that covers its bounds, keeping
+    /// its aspect ratio, and centers it. The edges that don't fit are cut off.
+    Cover,
+    /// Scales the image to the size of its bounds, ignoring its aspect ratio.
+    Fill,
+    /// Draws the image at its size (multiplied by its scale) in the center of
+    /// its bounds.
+    Center,
+}
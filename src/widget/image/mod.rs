@@ -0,0 +1,396 @@
+mod color_map;
+mod color_mapped;
+mod image_fit;
+mod nine_patch;
+mod patch_fill;
+mod scaled;
+
+pub use color_map::{ColorMap, Convert, Dither, SameColor, Threshold, Tint};
+pub use image_fit::ImageFit;
+pub use nine_patch::NinePatch;
+pub use patch_fill::PatchFill;
+
+use super::{
+    container::Axis,
+    offset::{clamp_rectangle, Offset},
+    AspectRatio, IntrinsicSize, LayoutNode, LayoutOptions, Length, Widget,
+};
+use cherry_macros::Builder;
+use color_mapped::ColorMapped;
+use core::{
+    any::Any,
+    hash::{Hash, Hasher},
+    ptr,
+};
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+use scaled::Scaled;
+
+/// An image, sized and placed in its bounds according to its fit.
+///
+/// With `ImageFit::None` and `ImageFit::Center` the image has a fixed size,
+/// which is its size multiplied by its scale. With the other fits its
+/// intrinsic size is the size of the image, which the fit scales to the
+/// bounds it is given. `Contain` and `Cover` use the image's aspect ratio,
+/// unless the layout options have another one, so that a width or height in
+/// the layout options sizes the other dimension too.
+///
+/// The image is drawn in its own colors, unless it is given a color map (for
+/// example, to draw an `Rgb888` image on a `BinaryColor` display).
+#[derive(Clone, Copy, Builder)]
+pub struct Image<'a, T, Map = SameColor>
+where
+    T: ImageDrawable,
+{
+    #[omit]
+    color_map: Map,
+    fit: ImageFit,
+    #[omit]
+    image: &'a T,
+    layout_options: LayoutOptions,
+    /// The factor the image is scaled up by with `ImageFit::None` and
+    /// `ImageFit::Center`.
+    scale: u32,
+}
+
+impl<'a, T> Image<'a, T>
+where
+    T: ImageDrawable,
+{
+    pub fn new(image: &'a T) -> Self {
+        Self {
+            color_map: SameColor,
+            fit: Default::default(),
+            image,
+            layout_options: Default::default(),
+            scale: 1,
+        }
+    }
+}
+
+impl<'a, T, Map> Image<'a, T, Map>
+where
+    T: ImageDrawable,
+{
+    /// Maps the colors of the image to the colors of the display with
+    /// `color_map`.
+    pub fn color_map<NewMap>(self, color_map: NewMap) -> Image<'a, T, NewMap> {
+        Image {
+            color_map,
+            fit: self.fit,
+            image: self.image,
+            layout_options: self.layout_options,
+            scale: self.scale,
+        }
+    }
+
+    fn aspect_ratio(&self) -> AspectRatio {
+        let size = self.image.size();
+        AspectRatio::new(size.width, size.height)
+    }
+
+    fn scaled_size(&self) -> Size {
+        let size = self.image.size();
+        Size::new(
+            size.width.saturating_mul(self.scale),
+            size.height.saturating_mul(self.scale),
+        )
+    }
+
+    /// Where the whole image is drawn when it is placed in `bounds`. This can
+    /// extend past the bounds.
+    fn destination(&self, bounds: Rectangle) -> Rectangle {
+        let available = bounds.size;
+
+        let size = match self.fit {
+            ImageFit::None | ImageFit::Center => self.scaled_size(),
+            ImageFit::Fill => available,
+            ImageFit::Contain | ImageFit::Cover => {
+                let image_size = self.image.size();
+                // whether the image is wider than the bounds, relative to
+                // their heights
+                let wider = image_size.width as u64 * available.height as u64
+                    >= image_size.height as u64 * available.width as u64;
+
+                if wider == (self.fit == ImageFit::Contain) {
+                    Size::new(
+                        available.width,
+                        self.aspect_ratio()
+                            .dimension_for_axis(available.width, Axis::Vertical),
+                    )
+                } else {
+                    Size::new(
+                        self.aspect_ratio()
+                            .dimension_for_axis(available.height, Axis::Horizontal),
+                        available.height,
+                    )
+                }
+            }
+        };
+
+        let offset = match self.fit {
+            ImageFit::None => Point::zero(),
+            _ => {
+                let center =
+                    |available: u32, size: u32| ((available as i64 - size as i64) / 2) as i32;
+                Point::new(
+                    center(available.width, size.width),
+                    center(available.height, size.height),
+                )
+            }
+        };
+
+        Rectangle::new(bounds.top_left.saturating_add(offset), size)
+    }
+
+    /// The part of the image that ends up in `visible`, when the whole image
+    /// is drawn in `destination`.
+    fn source_area(&self, visible: Rectangle, destination: Rectangle) -> Rectangle {
+        let image_size = self.image.size();
+
+        let range =
+            |start: i32, size: u32, destination_start: i32, source: u32, destination: u32| {
+                let (source, destination) = (source as i64, destination.max(1) as i64);
+                let start = (start as i64 - destination_start as i64).max(0);
+                let end = start + size as i64;
+
+                let source_start = (start * source / destination).min(source);
+                let source_end = ((end * source + destination - 1) / destination).min(source);
+                (source_start as i32, (source_end - source_start) as u32)
+            };
+
+        let (x, width) = range(
+            visible.top_left.x,
+            visible.size.width,
+            destination.top_left.x,
+            image_size.width,
+            destination.size.width,
+        );
+        let (y, height) = range(
+            visible.top_left.y,
+            visible.size.height,
+            destination.top_left.y,
+            image_size.height,
+            destination.size.height,
+        );
+
+        Rectangle::new(Point::new(x, y), Size::new(width, height))
+    }
+}
+
+impl<'a, T, Map, Display> Widget<Display> for Image<'a, T, Map>
+where
+    T: 'a + ImageDrawable,
+    Map: ColorMap<T::Color, Display::Color>,
+    Display: DrawTarget,
+{
+    fn intrinsic_size(&self) -> IntrinsicSize {
+        match self.fit {
+            ImageFit::None | ImageFit::Center => self.scaled_size().into(),
+            ImageFit::Contain | ImageFit::Cover => {
+                match (self.layout_options.width, self.layout_options.height) {
+                    (Length::Auto, Length::Auto) => self.image.size().into(),
+                    // the aspect ratio gives the dimension that isn't set
+                    _ => IntrinsicSize::none(),
+                }
+            }
+            ImageFit::Fill => self.image.size().into(),
+        }
+    }
+
+    fn layout_options(&self) -> LayoutOptions {
+        match self.fit {
+            ImageFit::Contain | ImageFit::Cover => LayoutOptions {
+                aspect_ratio: self
+                    .layout_options
+                    .aspect_ratio
+                    .or_else(|| Some(self.aspect_ratio())),
+                ..self.layout_options
+            },
+            ImageFit::None | ImageFit::Center | ImageFit::Fill => self.layout_options,
+        }
+    }
+
+    /// The image is compared by address, not by content, and changes of
+    /// color map are only redrawn when they are made with `Tree::update`.
+    fn hash_appearance(&self, mut state: &mut dyn Hasher) {
+        ptr::hash(self.image, &mut state);
+        self.fit.hash(&mut state);
+        self.scale.hash(&mut state);
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn draw_clipped(
+        &self,
+        display: &mut Display,
+        layout: &LayoutNode,
+        clip: Rectangle,
+    ) -> Result<(), Display::Error> {
+        let bounds = layout.bounds.intersection(&clip);
+        let destination = self.destination(layout.bounds);
+        let visible = clamp_rectangle(destination).intersection(&bounds);
+
+        if visible.is_zero_sized() {
+            return Ok(());
+        }
+
+        // only the part of the image that ends up in the bounds is drawn
+        let source = self.source_area(visible, destination);
+        let mut clipped = display.clipped(&bounds);
+        let mut display = ColorMapped::new(&mut clipped, &self.color_map);
+
+        if destination.size == self.image.size() {
+            self.image.draw_sub_image(
+                &mut display.translated(destination.top_left.saturating_add(source.top_left)),
+                &source,
+            )
+        } else {
+            self.image.draw_sub_image(
+                &mut Scaled::new(&mut display, source, self.image.size(), destination),
+                &source,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::container::Container;
+    use alloc::boxed::Box;
+    use embedded_graphics::{
+        image::ImageRaw,
+        mock_display::MockDisplay,
+        pixelcolor::{BinaryColor, Gray8, Rgb565, Rgb888},
+    };
+
+    // a 2x2 image with the top left and bottom right pixels set
+    const DIAGONAL: [u8; 2] = [0b1000_0000, 0b0100_0000];
+
+    #[test]
+    fn destination() {
+        let raw = ImageRaw::<BinaryColor>::new(&[0; 2], 4);
+        let image = Image::new(&raw);
+        let bounds = Rectangle::new(Point::new(10, 10), Size::new(8, 4));
+
+        let destination = |fit| image.fit(fit).destination(bounds);
+        assert_eq!(
+            destination(ImageFit::None),
+            Rectangle::new(Point::new(10, 10), Size::new(4, 2))
+        );
+        assert_eq!(
+            image.scale(3).fit(ImageFit::Center).destination(bounds),
+            Rectangle::new(Point::new(8, 9), Size::new(12, 6))
+        );
+        assert_eq!(
+            destination(ImageFit::Contain),
+            Rectangle::new(Point::new(10, 10), Size::new(8, 4))
+        );
+        assert_eq!(
+            image
+                .fit(ImageFit::Contain)
+                .destination(Rectangle::new(Point::zero(), Size::new(8, 8))),
+            Rectangle::new(Point::new(0, 2), Size::new(8, 4))
+        );
+        assert_eq!(
+            image
+                .fit(ImageFit::Cover)
+                .destination(Rectangle::new(Point::zero(), Size::new(8, 8))),
+            Rectangle::new(Point::new(-4, 0), Size::new(16, 8))
+        );
+        assert_eq!(destination(ImageFit::Fill), bounds);
+    }
+
+    #[test]
+    fn draw_scaled() {
+        let raw = ImageRaw::<BinaryColor>::new(&DIAGONAL, 2);
+
+        let mut display = MockDisplay::new();
+        Image::new(&raw)
+            .fit(ImageFit::Fill)
+            .draw(&mut display, Point::zero(), Size::new(4, 4))
+            .unwrap();
+        display.assert_pattern(&["##..", "##..", "..##", "..##"]);
+
+        let mut display = MockDisplay::new();
+        Image::new(&raw)
+            .scale(2)
+            .fit(ImageFit::Center)
+            .draw(&mut display, Point::zero(), Size::new(2, 2))
+            .unwrap();
+        display.assert_pattern(&["#.", ".#"]);
+
+        let mut display = MockDisplay::new();
+        Image::new(&raw)
+            .fit(ImageFit::Cover)
+            .draw(&mut display, Point::zero(), Size::new(3, 1))
+            .unwrap();
+        display.assert_pattern(&[".##"]);
+    }
+
+    #[test]
+    fn aspect_ratio_in_container() {
+        // boxed widgets can't borrow from the stack
+        let raw: &'static _ = Box::leak(Box::new(ImageRaw::<BinaryColor>::new(&[0; 2], 4)));
+        let image = Image::new(raw).fit(ImageFit::Contain);
+        let container = Container::<MockDisplay<BinaryColor>>::new().children(vec![
+            image.boxed(),
+            image
+                .layout_options(LayoutOptions::new().width(Length::Percent(100)))
+                .boxed(),
+        ]);
+
+        let layout = container.layout(Rectangle::new(Point::zero(), Size::new(20, 20)));
+
+        assert_eq!(
+            layout.without_fingerprints().children,
+            vec![
+                LayoutNode::new(Rectangle::new(Point::zero(), Size::new(4, 2))),
+                LayoutNode::new(Rectangle::new(Point::new(0, 2), Size::new(20, 10))),
+            ]
+        );
+    }
+
+    #[test]
+    fn color_maps() {
+        let mask = ImageRaw::<BinaryColor>::new(&DIAGONAL, 2);
+        let mut display = MockDisplay::<Rgb888>::new();
+        Image::new(&mask)
+            .color_map(Tint::new(Rgb888::RED))
+            .draw(&mut display, Point::zero(), Size::new(2, 2))
+            .unwrap();
+        display.assert_pattern(&["R ", " R"]);
+
+        let rgb = ImageRaw::<Rgb888>::new(&[255, 255, 255, 0, 0, 0], 2);
+        let mut display = MockDisplay::<BinaryColor>::new();
+        Image::new(&rgb)
+            .color_map(Threshold::default())
+            .draw(&mut display, Point::zero(), Size::new(2, 1))
+            .unwrap();
+        display.assert_pattern(&["#."]);
+
+        let mut display = MockDisplay::<Rgb565>::new();
+        Image::new(&rgb)
+            .color_map(Convert(|color: Rgb888| Rgb565::from(color)))
+            .draw(&mut display, Point::zero(), Size::new(2, 1))
+            .unwrap();
+        display.assert_pattern(&["WK"]);
+    }
+
+    #[test]
+    fn dither() {
+        let gray = ImageRaw::<Gray8>::new(&[128; 16], 4);
+        let mut display = MockDisplay::<BinaryColor>::new();
+
+        Image::new(&gray)
+            .color_map(Dither)
+            .draw(&mut display, Point::zero(), Size::new(4, 4))
+            .unwrap();
+        display.assert_pattern(&["#.#.", ".#.#", "#.#.", ".#.#"]);
+    }
+}
@@ -0,0 +1,206 @@
+use super::{scaled::Scaled, PatchFill};
+use crate::widget::{
+    container::Insets, offset::Offset, IntrinsicSize, LayoutNode, LayoutOptions, Widget,
+};
+use cherry_macros::Builder;
+use core::{
+    any::Any,
+    hash::{Hash, Hasher},
+    ptr,
+};
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+/// An image that is cut into nine patches by the `slices` from each edge. The
+/// corners are drawn at their size, and the edges and the center fill the
+/// rest of the bounds, so that the image can frame content of any size (such
+/// as the background of a `Container`).
+#[derive(Clone, Copy, Builder)]
+pub struct NinePatch<'a, T>
+where
+    T: ImageDrawable,
+{
+    fill: PatchFill,
+    #[omit]
+    image: &'a T,
+    layout_options: LayoutOptions,
+    #[omit]
+    slices: Insets,
+}
+
+impl<'a, T> NinePatch<'a, T>
+where
+    T: ImageDrawable,
+{
+    pub fn new(image: &'a T, slices: Insets) -> Self {
+        Self {
+            fill: Default::default(),
+            image,
+            layout_options: Default::default(),
+            slices,
+        }
+    }
+
+    /// The patches of the image, along with the rectangles they fill in
+    /// `bounds`.
+    fn patches(&self, bounds: Rectangle) -> impl Iterator<Item = (Rectangle, Rectangle)> {
+        let ranges = |start: u32, end: u32, size: u32| {
+            let middle = size.saturating_sub(start).saturating_sub(end);
+            [
+                (0, start.min(size)),
+                (start.min(size), middle),
+                (start.saturating_add(middle).min(size), end.min(size)),
+            ]
+        };
+
+        let size = self.image.size();
+        let source_columns = ranges(self.slices.left, self.slices.right, size.width);
+        let source_rows = ranges(self.slices.top, self.slices.bottom, size.height);
+        let columns = ranges(self.slices.left, self.slices.right, bounds.size.width);
+        let rows = ranges(self.slices.top, self.slices.bottom, bounds.size.height);
+
+        let rectangle = |origin: Point, (x, width): (u32, u32), (y, height): (u32, u32)| {
+            Rectangle::new(origin.offset(x, y), Size::new(width, height))
+        };
+
+        (0..3).flat_map(move |row| {
+            (0..3).map(move |column| {
+                (
+                    rectangle(Point::zero(), source_columns[column], source_rows[row]),
+                    rectangle(bounds.top_left, columns[column], rows[row]),
+                )
+            })
+        })
+    }
+
+    fn draw_patch<Display>(
+        &self,
+        display: &mut Display,
+        source: Rectangle,
+        destination: Rectangle,
+    ) -> Result<(), Display::Error>
+    where
+        Display: DrawTarget<Color = T::Color>,
+    {
+        if source.is_zero_sized() || destination.is_zero_sized() {
+            return Ok(());
+        }
+
+        if source.size == destination.size {
+            return self
+                .image
+                .draw_sub_image(&mut display.translated(destination.top_left), &source);
+        }
+
+        match self.fill {
+            PatchFill::Stretch => self.image.draw_sub_image(
+                &mut Scaled::new(
+                    display,
+                    Rectangle::new(Point::zero(), source.size),
+                    source.size,
+                    destination,
+                ),
+                &source,
+            ),
+            PatchFill::Tile => {
+                let mut display = display.clipped(&destination);
+
+                for y in (0..destination.size.height).step_by(source.size.height as usize) {
+                    for x in (0..destination.size.width).step_by(source.size.width as usize) {
+                        let origin = destination.top_left.offset(x, y);
+                        self.image
+                            .draw_sub_image(&mut display.translated(origin), &source)?;
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<'a, T, Display> Widget<Display> for NinePatch<'a, T>
+where
+    T: 'a + ImageDrawable,
+    Display: DrawTarget<Color = T::Color>,
+{
+    /// A nine-patch has no intrinsic size, but can't be smaller than its
+    /// corners.
+    fn intrinsic_size(&self) -> IntrinsicSize {
+        IntrinsicSize::none()
+    }
+
+    fn layout_options(&self) -> LayoutOptions {
+        let corners = self.slices.total();
+
+        LayoutOptions {
+            min_width: self.layout_options.min_width.or(Some(corners.width)),
+            min_height: self.layout_options.min_height.or(Some(corners.height)),
+            ..self.layout_options
+        }
+    }
+
+    /// The image is compared by address, not by content.
+    fn hash_appearance(&self, mut state: &mut dyn Hasher) {
+        ptr::hash(self.image, &mut state);
+        self.fill.hash(&mut state);
+        self.slices.hash(&mut state);
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn draw_clipped(
+        &self,
+        display: &mut Display,
+        layout: &LayoutNode,
+        clip: Rectangle,
+    ) -> Result<(), Display::Error> {
+        let area = layout.bounds.intersection(&clip);
+
+        if area.is_zero_sized() {
+            return Ok(());
+        }
+
+        let mut display = display.clipped(&area);
+
+        for (source, destination) in self.patches(layout.bounds) {
+            self.draw_patch(&mut display, source, destination)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::{image::ImageRaw, mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    // #..#
+    // .#..
+    // #..#
+    const FRAME: [u8; 3] = [0b1001_0000, 0b0100_0000, 0b1001_0000];
+
+    #[test]
+    fn draw() {
+        let raw = ImageRaw::<BinaryColor>::new(&FRAME, 4);
+        let nine_patch = NinePatch::new(&raw, Insets::all(1));
+
+        let mut display = MockDisplay::new();
+        nine_patch
+            .draw(&mut display, Point::zero(), Size::new(6, 3))
+            .unwrap();
+        display.assert_pattern(&["#....#", ".##...", "#....#"]);
+
+        let mut display = MockDisplay::new();
+        nine_patch
+            .fill(PatchFill::Tile)
+            .draw(&mut display, Point::zero(), Size::new(6, 3))
+            .unwrap();
+        display.assert_pattern(&["#....#", ".#.#..", "#....#"]);
+    }
+}
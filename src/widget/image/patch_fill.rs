@@ -0,0 +1,12 @@
+/// How the edges and center of a nine-patch fill the space between its
+/// corners.
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub enum PatchFill {
+    /// Scales each patch to the space it fills, using nearest neighbour
+    /// scaling.
+    #[default]
+    Stretch,
+    /// Repeats each patch at its size, cutting off the last repetition.
+    Tile,
+}
@@ -0,0 +1,91 @@
+use crate::widget::offset::Offset;
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+/// A draw target that scales everything drawn on it from `source` to the
+/// size of `destination`, using nearest neighbour scaling. Each source pixel
+/// fills the destination pixels it covers, so scaling up draws blocks and
+/// scaling down skips pixels.
+///
+/// Points are relative to the top left corner of `source`, which is the
+/// area of the image drawn with `ImageDrawable::draw_sub_image`.
+pub(super) struct Scaled<'a, Display> {
+    display: &'a mut Display,
+    source: Rectangle,
+    source_size: Size,
+    destination: Rectangle,
+}
+
+impl<'a, Display> Scaled<'a, Display>
+where
+    Display: DrawTarget,
+{
+    /// `source` is the part of an image of `source_size` that is drawn, and
+    /// `destination` is where the whole image is scaled to.
+    pub fn new(
+        display: &'a mut Display,
+        source: Rectangle,
+        source_size: Size,
+        destination: Rectangle,
+    ) -> Self {
+        Self {
+            display,
+            source,
+            source_size,
+            destination,
+        }
+    }
+
+    fn scale(value: i32, source: u32, destination: u32) -> i32 {
+        (value as i64 * destination as i64)
+            .checked_div(source as i64)
+            .unwrap_or(0)
+            .clamp(i32::MIN as i64, i32::MAX as i64) as i32
+    }
+
+    /// The destination pixels covered by the source pixel at `point`, which
+    /// are empty for some pixels when scaling down.
+    fn scaled_area(&self, point: Point) -> Rectangle {
+        let x = |x| Self::scale(x, self.source_size.width, self.destination.size.width);
+        let y = |y| Self::scale(y, self.source_size.height, self.destination.size.height);
+
+        let top_left = Point::new(x(point.x), y(point.y));
+        let size = Size::new(
+            x(point.x.saturating_add(1)).saturating_sub(top_left.x) as u32,
+            y(point.y.saturating_add(1)).saturating_sub(top_left.y) as u32,
+        );
+
+        Rectangle::new(self.destination.top_left.saturating_add(top_left), size)
+    }
+}
+
+impl<Display> Dimensions for Scaled<'_, Display> {
+    fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(Point::zero(), self.source.size)
+    }
+}
+
+impl<Display> DrawTarget for Scaled<'_, Display>
+where
+    Display: DrawTarget,
+{
+    type Color = Display::Color;
+    type Error = Display::Error;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        let bounding_box = self.bounding_box();
+
+        for Pixel(point, color) in pixels {
+            if !bounding_box.contains(point) {
+                continue;
+            }
+
+            self.display
+                .fill_solid(&self.scaled_area(point + self.source.top_left), color)?;
+        }
+
+        Ok(())
+    }
+}
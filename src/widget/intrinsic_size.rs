@@ -30,6 +30,16 @@ impl IntrinsicSize {
         }
     }
 
+    /// How far the intrinsic size exceeds `size` on each axis.
+    pub fn overflow(&self, size: Size) -> Size {
+        Size::new(
+            self.width
+                .map_or(0, |width| width.saturating_sub(size.width)),
+            self.height
+                .map_or(0, |height| height.saturating_sub(size.height)),
+        )
+    }
+
     pub fn to_size_with_defaults(&self, default: Size) -> Size {
         Size::new(
             self.width.unwrap_or(default.width),
@@ -48,8 +58,8 @@ impl AxisSize<Option<u32>> for IntrinsicSize {
 
     fn add_to_axis(&mut self, value: u32, axis: Axis) {
         match axis {
-            Axis::Horizontal => self.width = self.width.map(|width| width + value),
-            Axis::Vertical => self.height = self.height.map(|height| height + value),
+            Axis::Horizontal => self.width = self.width.map(|width| width.saturating_add(value)),
+            Axis::Vertical => self.height = self.height.map(|height| height.saturating_add(value)),
         }
     }
 }
@@ -57,19 +67,23 @@ impl AxisSize<Option<u32>> for IntrinsicSize {
 impl Inset for IntrinsicSize {
     fn inset(&self, insets: Insets) -> Self {
         Self {
-            width: self.width.map(|width| width - insets.left - insets.right),
+            width: self
+                .width
+                .map(|width| width.saturating_sub(insets.horizontal_total())),
             height: self
                 .height
-                .map(|height| height - insets.top - insets.bottom),
+                .map(|height| height.saturating_sub(insets.vertical_total())),
         }
     }
 
     fn outset(&self, insets: Insets) -> Self {
         Self {
-            width: self.width.map(|width| width + insets.left + insets.right),
+            width: self
+                .width
+                .map(|width| width.saturating_add(insets.horizontal_total())),
             height: self
                 .height
-                .map(|height| height + insets.top + insets.bottom),
+                .map(|height| height.saturating_add(insets.vertical_total())),
         }
     }
 }
@@ -0,0 +1,11 @@
+/// Identifies a widget in a `Tree`, so that it can be updated in place. Keys
+/// are set in the widget's layout options, and should be unique in the tree.
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub struct Key(pub u32);
+
+impl From<u32> for Key {
+    fn from(key: u32) -> Self {
+        Self(key)
+    }
+}
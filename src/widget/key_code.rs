@@ -0,0 +1,13 @@
+/// A button on the device, sent with `Event::Key`.
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub enum KeyCode {
+    Up,
+    Down,
+    Left,
+    Right,
+    Select,
+    Back,
+    /// Any other button, identified by the application.
+    Other(u32),
+}
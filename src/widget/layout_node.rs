@@ -0,0 +1,236 @@
+use super::{offset::clamp_rectangle, DirtyRegions, Overflow};
+use alloc::vec::Vec;
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+/// The result of the arrange pass: the rectangle a widget occupies, along with
+/// the layouts of its children (in the same order as the children).
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub struct LayoutNode {
+    pub bounds: Rectangle,
+    pub children: Vec<LayoutNode>,
+    /// The widget's `Widget::fingerprint`, which changes when its appearance
+    /// does.
+    pub fingerprint: u64,
+    /// How far the widget's content extends beyond its bounds on each axis.
+    pub overflow: Size,
+}
+
+impl LayoutNode {
+    /// Bounds that extend past the edge of the coordinate space (which a
+    /// saturated layout can produce) are shrunk to end at the edge.
+    pub fn new(bounds: Rectangle) -> Self {
+        Self {
+            bounds: clamp_rectangle(bounds),
+            children: Vec::new(),
+            fingerprint: 0,
+            overflow: Size::zero(),
+        }
+    }
+
+    pub fn with_children(bounds: Rectangle, children: Vec<LayoutNode>) -> Self {
+        Self {
+            bounds: clamp_rectangle(bounds),
+            children,
+            fingerprint: 0,
+            overflow: Size::zero(),
+        }
+    }
+
+    pub fn with_overflow(mut self, overflow: Size) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    pub fn with_fingerprint(mut self, fingerprint: u64) -> Self {
+        self.fingerprint = fingerprint;
+        self
+    }
+
+    pub fn origin(&self) -> Point {
+        self.bounds.top_left
+    }
+
+    pub fn size(&self) -> Size {
+        self.bounds.size
+    }
+
+    /// How far the children extend beyond `area` on each axis, on either side.
+    pub fn children_overflow(&self, area: Rectangle) -> Size {
+        self.children.iter().fold(Size::zero(), |overflow, child| {
+            overflow.component_max(extent_beyond(child.bounds, area))
+        })
+    }
+
+    /// The index of the child containing `point`. When children overlap, the
+    /// last one (which is drawn on top) is chosen.
+    pub fn child_at(&self, point: Point) -> Option<usize> {
+        self.children
+            .iter()
+            .rposition(|child| child.bounds.contains(point))
+    }
+
+    /// Collects every widget in the layout that overflowed its bounds.
+    pub fn overflows(&self) -> Vec<Overflow> {
+        let mut overflows = Vec::new();
+        let mut path = Vec::new();
+        self.collect_overflows(&mut path, &mut overflows);
+        overflows
+    }
+
+    /// The regions that changed since the `previous` layout of the same tree:
+    /// the old and new bounds of every widget that moved, resized, changed
+    /// its appearance or changed its number of children.
+    pub fn dirty_regions(&self, previous: &LayoutNode) -> DirtyRegions {
+        let mut regions = DirtyRegions::new();
+        self.collect_dirty_regions(previous, &mut regions);
+        regions
+    }
+
+    /// The layout with every fingerprint reset, for comparing layouts by
+    /// geometry alone.
+    #[cfg(test)]
+    pub(crate) fn without_fingerprints(&self) -> Self {
+        Self {
+            children: self
+                .children
+                .iter()
+                .map(LayoutNode::without_fingerprints)
+                .collect(),
+            fingerprint: 0,
+            ..*self
+        }
+    }
+
+    pub(super) fn collect_dirty_regions(&self, previous: &LayoutNode, regions: &mut DirtyRegions) {
+        if self.bounds != previous.bounds
+            || self.fingerprint != previous.fingerprint
+            || self.children.len() != previous.children.len()
+        {
+            regions.add(previous.bounds);
+            regions.add(self.bounds);
+            return;
+        }
+
+        for (child, previous_child) in self.children.iter().zip(&previous.children) {
+            child.collect_dirty_regions(previous_child, regions);
+        }
+    }
+
+    fn collect_overflows(&self, path: &mut Vec<usize>, overflows: &mut Vec<Overflow>) {
+        if self.overflow != Size::zero() {
+            overflows.push(Overflow {
+                path: path.clone(),
+                bounds: self.bounds,
+                amount: self.overflow,
+            });
+        }
+
+        for (index, child) in self.children.iter().enumerate() {
+            path.push(index);
+            child.collect_overflows(path, overflows);
+            path.pop();
+        }
+    }
+}
+
+fn extent_beyond(inner: Rectangle, outer: Rectangle) -> Size {
+    let beyond = |inner_start: i32, inner_size: u32, outer_start: i32, outer_size: u32| {
+        let before = outer_start.saturating_sub(inner_start).max(0) as u32;
+        let after = (inner_start as i64 + inner_size as i64)
+            .saturating_sub(outer_start as i64 + outer_size as i64)
+            .max(0) as u32;
+        before.max(after)
+    };
+
+    Size::new(
+        beyond(
+            inner.top_left.x,
+            inner.size.width,
+            outer.top_left.x,
+            outer.size.width,
+        ),
+        beyond(
+            inner.top_left.y,
+            inner.size.height,
+            outer.top_left.y,
+            outer.size.height,
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overflows() {
+        let child = LayoutNode::new(Rectangle::new(Point::new(5, -2), Size::new(10, 4)));
+        let mut root = LayoutNode::with_children(
+            Rectangle::new(Point::zero(), Size::new(12, 10)),
+            vec![
+                LayoutNode::new(Rectangle::new(Point::zero(), Size::new(1, 1))),
+                child,
+            ],
+        );
+        root.overflow = root.children_overflow(root.bounds);
+
+        assert_eq!(root.overflow, Size::new(3, 2));
+        assert_eq!(
+            root.overflows(),
+            vec![Overflow {
+                path: vec![],
+                bounds: root.bounds,
+                amount: Size::new(3, 2),
+            }]
+        );
+
+        root.children[1].overflow = Size::new(1, 0);
+        assert_eq!(root.overflows()[1].path, vec![1]);
+    }
+
+    #[test]
+    fn child_at() {
+        let child = |x| LayoutNode::new(Rectangle::new(Point::new(x, 0), Size::new(10, 10)));
+        let root = LayoutNode::with_children(
+            Rectangle::new(Point::zero(), Size::new(20, 10)),
+            vec![child(0), child(5)],
+        );
+
+        assert_eq!(root.child_at(Point::new(2, 2)), Some(0));
+        assert_eq!(root.child_at(Point::new(7, 2)), Some(1));
+        assert_eq!(root.child_at(Point::new(20, 2)), None);
+    }
+
+    #[test]
+    fn dirty_regions() {
+        let child = |x, fingerprint| {
+            LayoutNode::new(Rectangle::new(Point::new(x, 0), Size::new(4, 4)))
+                .with_fingerprint(fingerprint)
+        };
+        let root = |children| {
+            LayoutNode::with_children(Rectangle::new(Point::zero(), Size::new(20, 4)), children)
+        };
+        let previous = root(vec![child(0, 1), child(10, 1)]);
+
+        assert!(previous.dirty_regions(&previous).is_empty());
+        assert_eq!(
+            root(vec![child(0, 1), child(10, 2)])
+                .dirty_regions(&previous)
+                .rectangles(),
+            &[child(10, 0).bounds]
+        );
+        assert_eq!(
+            root(vec![child(2, 1), child(10, 1)])
+                .dirty_regions(&previous)
+                .rectangles(),
+            &[Rectangle::new(Point::zero(), Size::new(6, 4))]
+        );
+        assert_eq!(
+            root(vec![child(0, 1)])
+                .dirty_regions(&previous)
+                .rectangles(),
+            &[previous.bounds]
+        );
+    }
+}
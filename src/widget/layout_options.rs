@@ -1,14 +1,195 @@
-use super::container::Alignment;
+use super::{
+    container::{Alignment, Axis},
+    AspectRatio, IntrinsicSize, Key, Length, Position,
+};
 use cherry_macros::Builder;
+use embedded_graphics::prelude::*;
 
-#[derive(Clone, Copy, Builder, Default)]
+#[derive(Clone, Copy, Builder, Default, Eq, PartialEq)]
 pub struct LayoutOptions {
     pub alignment: Option<Alignment>,
+    pub aspect_ratio: Option<AspectRatio>,
+    pub bottom: Option<i32>,
     pub grow: u32,
+    #[into]
+    pub height: Length,
+    /// Identifies the widget in a `Tree`.
+    #[into]
+    pub key: Option<Key>,
+    pub left: Option<i32>,
+    pub max_height: Option<u32>,
+    pub max_width: Option<u32>,
+    pub min_height: Option<u32>,
+    pub min_width: Option<u32>,
+    pub position: Position,
+    pub right: Option<i32>,
+    pub shrink: u32,
+    pub top: Option<i32>,
+    #[into]
+    pub width: Length,
 }
 
 impl LayoutOptions {
     pub fn new() -> Self {
         Default::default()
     }
+
+    pub fn min_for_axis(&self, axis: Axis) -> Option<u32> {
+        match axis {
+            Axis::Horizontal => self.min_width,
+            Axis::Vertical => self.min_height,
+        }
+    }
+
+    pub fn max_for_axis(&self, axis: Axis) -> Option<u32> {
+        match axis {
+            Axis::Horizontal => self.max_width,
+            Axis::Vertical => self.max_height,
+        }
+    }
+
+    /// Clamps `value` between the minimum and maximum for `axis`. The minimum
+    /// wins if it is larger than the maximum.
+    pub fn clamp_for_axis(&self, value: u32, axis: Axis) -> u32 {
+        let value = self.max_for_axis(axis).map_or(value, |max| value.min(max));
+        self.min_for_axis(axis).map_or(value, |min| value.max(min))
+    }
+
+    /// The offsets from the start and end edges for `axis`.
+    pub fn offsets_for_axis(&self, axis: Axis) -> (Option<i32>, Option<i32>) {
+        match axis {
+            Axis::Horizontal => (self.left, self.right),
+            Axis::Vertical => (self.top, self.bottom),
+        }
+    }
+
+    /// The amount a relatively positioned widget is moved by. The `left` and
+    /// `top` offsets take precedence over `right` and `bottom`.
+    pub fn relative_offset(&self) -> Point {
+        let offset = |(start, end): (Option<i32>, Option<i32>)| {
+            start.or_else(|| end.map(i32::saturating_neg)).unwrap_or(0)
+        };
+
+        Point::new(
+            offset(self.offsets_for_axis(Axis::Horizontal)),
+            offset(self.offsets_for_axis(Axis::Vertical)),
+        )
+    }
+
+    /// Applies the `width` and `height` (resolved against `available` space,
+    /// when it is known) to a widget's intrinsic size, fills in a missing
+    /// dimension using the aspect ratio, and clamps the result.
+    pub fn resolve_intrinsic_size(
+        &self,
+        size: IntrinsicSize,
+        available: Option<Size>,
+    ) -> IntrinsicSize {
+        let width = self
+            .width
+            .resolve(available.map(|available| available.width))
+            .or(size.width);
+        let height = self
+            .height
+            .resolve(available.map(|available| available.height))
+            .or(size.height);
+
+        self.clamp_intrinsic_size(self.apply_aspect_ratio(IntrinsicSize::new(width, height)))
+    }
+
+    /// Fills in the missing dimension of `size` using the aspect ratio, when
+    /// exactly one of the dimensions is known.
+    pub fn apply_aspect_ratio(&self, size: IntrinsicSize) -> IntrinsicSize {
+        match (self.aspect_ratio, size.width, size.height) {
+            (Some(ratio), Some(width), None) => IntrinsicSize::new(
+                Some(width),
+                Some(ratio.dimension_for_axis(width, Axis::Vertical)),
+            ),
+            (Some(ratio), None, Some(height)) => IntrinsicSize::new(
+                Some(ratio.dimension_for_axis(height, Axis::Horizontal)),
+                Some(height),
+            ),
+            _ => size,
+        }
+    }
+
+    pub fn clamp_intrinsic_size(&self, size: IntrinsicSize) -> IntrinsicSize {
+        IntrinsicSize::new(
+            size.width
+                .map(|width| self.clamp_for_axis(width, Axis::Horizontal)),
+            size.height
+                .map(|height| self.clamp_for_axis(height, Axis::Vertical)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamp_for_axis() {
+        let options = LayoutOptions::new().min_width(10).max_width(20);
+        assert_eq!(options.clamp_for_axis(5, Axis::Horizontal), 10);
+        assert_eq!(options.clamp_for_axis(15, Axis::Horizontal), 15);
+        assert_eq!(options.clamp_for_axis(25, Axis::Horizontal), 20);
+        assert_eq!(options.clamp_for_axis(25, Axis::Vertical), 25);
+
+        let options = LayoutOptions::new().min_height(20).max_height(10);
+        assert_eq!(options.clamp_for_axis(15, Axis::Vertical), 20);
+    }
+
+    #[test]
+    fn relative_offset() {
+        let options = LayoutOptions::new().left(2).right(5).bottom(3);
+        assert_eq!(options.relative_offset(), Point::new(2, -3));
+    }
+
+    #[test]
+    fn resolve_intrinsic_size() {
+        let options = LayoutOptions::new()
+            .width(Length::Percent(50))
+            .height(10)
+            .max_height(5);
+        let size = IntrinsicSize::new(Some(1), None);
+
+        assert_eq!(
+            options.resolve_intrinsic_size(size, Some(Size::new(40, 40))),
+            IntrinsicSize::new(Some(20), Some(5))
+        );
+        assert_eq!(
+            options.resolve_intrinsic_size(size, None),
+            IntrinsicSize::new(Some(1), Some(5))
+        );
+    }
+
+    #[test]
+    fn apply_aspect_ratio() {
+        let options = LayoutOptions::new().aspect_ratio(AspectRatio::new(2, 1));
+
+        assert_eq!(
+            options.apply_aspect_ratio(IntrinsicSize::new(Some(10), None)),
+            IntrinsicSize::new(Some(10), Some(5))
+        );
+        assert_eq!(
+            options.apply_aspect_ratio(IntrinsicSize::new(None, Some(10))),
+            IntrinsicSize::new(Some(20), Some(10))
+        );
+        assert_eq!(
+            options.apply_aspect_ratio(IntrinsicSize::none()),
+            IntrinsicSize::none()
+        );
+    }
+
+    #[test]
+    fn clamp_intrinsic_size() {
+        let options = LayoutOptions::new().min_width(10).max_height(20);
+        assert_eq!(
+            options.clamp_intrinsic_size(IntrinsicSize::new(Some(5), Some(25))),
+            IntrinsicSize::new(Some(10), Some(20))
+        );
+        assert_eq!(
+            options.clamp_intrinsic_size(IntrinsicSize::none()),
+            IntrinsicSize::none()
+        );
+    }
 }
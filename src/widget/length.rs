@@ -0,0 +1,47 @@
+/// A length that is resolved against the space available to a widget during
+/// layout.
+#[derive(Clone, Copy, Default, Eq, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub enum Length {
+    /// Determined by the widget's content. In `Insets`, this is zero.
+    #[default]
+    Auto,
+    /// A number of pixels.
+    Px(u32),
+    /// A percentage of the available space.
+    Percent(u8),
+}
+
+impl Length {
+    /// Resolves the length against `available` space, returning `None` for
+    /// `Auto`, or for `Percent` when the available space isn't known.
+    pub fn resolve(&self, available: Option<u32>) -> Option<u32> {
+        match self {
+            Self::Auto => None,
+            Self::Px(pixels) => Some(*pixels),
+            Self::Percent(percent) => {
+                available.map(|available| (available as u64 * *percent as u64 / 100) as u32)
+            }
+        }
+    }
+}
+
+impl From<u32> for Length {
+    fn from(pixels: u32) -> Self {
+        Self::Px(pixels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve() {
+        assert_eq!(Length::Auto.resolve(Some(200)), None);
+        assert_eq!(Length::Px(10).resolve(Some(200)), Some(10));
+        assert_eq!(Length::Px(10).resolve(None), Some(10));
+        assert_eq!(Length::Percent(25).resolve(Some(200)), Some(50));
+        assert_eq!(Length::Percent(25).resolve(None), None);
+    }
+}
@@ -0,0 +1,27 @@
+use super::IntrinsicSize;
+use alloc::vec::Vec;
+
+/// The result of the measure pass: a widget's intrinsic size, along with the
+/// measurements of its children (in the same order as the children).
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub struct Measurement {
+    pub intrinsic_size: IntrinsicSize,
+    pub children: Vec<Measurement>,
+}
+
+impl Measurement {
+    pub fn new(intrinsic_size: IntrinsicSize) -> Self {
+        Self {
+            intrinsic_size,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn with_children(intrinsic_size: IntrinsicSize, children: Vec<Measurement>) -> Self {
+        Self {
+            intrinsic_size,
+            children,
+        }
+    }
+}
@@ -1,16 +1,49 @@
+pub mod button;
 pub mod container;
+pub mod grid;
 pub mod image;
+pub mod paragraph;
+pub mod progress_bar;
+pub mod stack;
 pub mod text;
 
+mod aspect_ratio;
 mod axis_size;
+mod direction;
+mod dirty_regions;
+mod event;
+mod fingerprint;
 mod intrinsic_size;
+mod key;
+mod key_code;
+mod layout_node;
 mod layout_options;
+mod length;
+mod measurement;
+mod offset;
+mod overflow;
+mod position;
+mod tree;
 
+pub use aspect_ratio::AspectRatio;
+pub use direction::Direction;
+pub use dirty_regions::DirtyRegions;
+pub use event::{Event, Message};
 pub use intrinsic_size::IntrinsicSize;
+pub use key::Key;
+pub use key_code::KeyCode;
+pub use layout_node::LayoutNode;
 pub use layout_options::LayoutOptions;
+pub use length::Length;
+pub use measurement::Measurement;
+pub use overflow::Overflow;
+pub use position::Position;
+pub use tree::Tree;
 
-use alloc::boxed::Box;
-use embedded_graphics::prelude::*;
+use alloc::{boxed::Box, vec::Vec};
+use core::{any::Any, hash::Hasher};
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+use fingerprint::Fingerprint;
 
 pub trait Widget<Display: DrawTarget> {
     fn intrinsic_size(&self) -> IntrinsicSize;
@@ -19,7 +52,166 @@ pub trait Widget<Display: DrawTarget> {
         LayoutOptions::default()
     }
 
-    fn draw(&self, display: &mut Display, origin: Point, size: Size) -> Result<(), Display::Error>;
+    /// The height the widget needs when it is `width` wide, for widgets whose
+    /// height depends on their width (such as wrapped text). Returns `None`
+    /// if the intrinsic height applies at every width. Widgets with children
+    /// should use the children's measurements from `measurement` (the result
+    /// of `measure`) rather than measuring them again.
+    fn height_for_width(&self, _measurement: &Measurement, _width: u32) -> Option<u32> {
+        None
+    }
+
+    /// Measures the widget and its children. Widgets with children should
+    /// measure each child exactly once, so that `arrange` can reuse the result.
+    fn measure(&self) -> Measurement {
+        Measurement::new(self.intrinsic_size())
+    }
+
+    /// Measures the widget using existing measurements of its children (in
+    /// the same order as the children), so that a `Tree` can measure a widget
+    /// again after one of its children changed without measuring the others.
+    /// Widgets with children should implement `measure` with this.
+    fn measure_with_children(&self, _children: Vec<Measurement>) -> Measurement {
+        self.measure()
+    }
+
+    /// Resolves the rectangles of the widget and its children, given the
+    /// result of `measure` and the bounds assigned by the parent.
+    fn arrange(&self, measurement: &Measurement, bounds: Rectangle) -> LayoutNode {
+        LayoutNode::new(bounds)
+            .with_overflow(measurement.intrinsic_size.overflow(bounds.size))
+            .with_fingerprint(self.fingerprint())
+    }
+
+    /// Hashes the parts of the widget's appearance that its layout doesn't
+    /// describe (such as its text), so that partial redraws can tell when it
+    /// has changed. It shouldn't include its children.
+    fn hash_appearance(&self, _state: &mut dyn Hasher) {}
+
+    /// A hash of `hash_appearance`, which is stored in the widget's layout
+    /// node during `arrange`.
+    fn fingerprint(&self) -> u64 {
+        let mut state = Fingerprint::new();
+        self.hash_appearance(&mut state);
+        state.finish()
+    }
+
+    /// The child at `index`, for widgets with children. Children are in the
+    /// same order as the children of the widget's layout node.
+    fn child_mut(&mut self, _index: usize) -> Option<&mut (dyn Widget<Display> + 'static)> {
+        None
+    }
+
+    /// The widget as `Any`, so that a `Tree` can update it in place. Widgets
+    /// that return `None` can't be updated.
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        None
+    }
+
+    /// Whether the widget can take focus, so that it can be reached with
+    /// keyboard and encoder navigation.
+    fn focusable(&self) -> bool {
+        false
+    }
+
+    /// Tells a focusable widget whether it has focus, so that it can draw
+    /// itself differently.
+    fn set_focused(&mut self, _focused: bool) {}
+
+    /// Whether the widget has focus, as last told by `set_focused`.
+    fn focused(&self) -> bool {
+        false
+    }
+
+    /// Handles an input event, given the widget's layout, and returns a
+    /// message for the application if the event produced one.
+    ///
+    /// By default, the event is sent on to the children. `TouchDown` goes to
+    /// the child under the touch point. `TouchMove` and `TouchUp` go to every
+    /// child, so that a child that was touched sees them even if the touch
+    /// has left it. Other events go to each child until one of them produces
+    /// a message.
+    fn event(&mut self, event: &Event, layout: &LayoutNode) -> Option<Message> {
+        match *event {
+            Event::TouchDown(point) => {
+                let index = layout.child_at(point)?;
+                self.child_mut(index)?.event(event, &layout.children[index])
+            }
+            Event::TouchMove(_) | Event::TouchUp(_) => {
+                let mut message = None;
+
+                for (index, child_layout) in layout.children.iter().enumerate() {
+                    let child_message = match self.child_mut(index) {
+                        Some(child) => child.event(event, child_layout),
+                        None => break,
+                    };
+                    message = message.or(child_message);
+                }
+
+                message
+            }
+            Event::Key(_) | Event::Encoder(_) => {
+                for (index, child_layout) in layout.children.iter().enumerate() {
+                    let message = self.child_mut(index)?.event(event, child_layout);
+
+                    if message.is_some() {
+                        return message;
+                    }
+                }
+
+                None
+            }
+        }
+    }
+
+    fn layout(&self, bounds: Rectangle) -> LayoutNode {
+        self.arrange(&self.measure(), bounds)
+    }
+
+    /// Draws the part of the widget inside `clip`, using a layout previously
+    /// produced by `layout`. Widgets with children pass `clip` on to them.
+    fn draw_clipped(
+        &self,
+        display: &mut Display,
+        layout: &LayoutNode,
+        clip: Rectangle,
+    ) -> Result<(), Display::Error>;
+
+    /// Draws the widget using a layout previously produced by `layout`.
+    fn draw_layout(
+        &self,
+        display: &mut Display,
+        layout: &LayoutNode,
+    ) -> Result<(), Display::Error> {
+        let clip = display.bounding_box();
+        self.draw_clipped(display, layout, clip)
+    }
+
+    fn draw(&self, display: &mut Display, origin: Point, size: Size) -> Result<(), Display::Error> {
+        let layout = self.layout(Rectangle::new(origin, size));
+        self.draw_layout(display, &layout)
+    }
+
+    /// Redraws only the parts of the widget in `regions` (which usually come
+    /// from `LayoutNode::dirty_regions`), after filling them with
+    /// `clear_color`.
+    fn draw_regions(
+        &self,
+        display: &mut Display,
+        layout: &LayoutNode,
+        regions: &DirtyRegions,
+        clear_color: Display::Color,
+    ) -> Result<(), Display::Error> {
+        for region in regions.rectangles() {
+            display.clipped(region).clear(clear_color)?;
+            self.draw_clipped(display, layout, *region)?;
+        }
+
+        Ok(())
+    }
 
     fn boxed(self) -> Box<dyn Widget<Display>>
     where
@@ -0,0 +1,74 @@
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+/// Moves points during layout, saturating at the edges of the coordinate
+/// space instead of overflowing.
+pub trait Offset {
+    /// The point moved right by `x` and down by `y`.
+    fn offset(self, x: u32, y: u32) -> Self;
+
+    fn saturating_add(self, other: Self) -> Self;
+}
+
+impl Offset for Point {
+    fn offset(self, x: u32, y: u32) -> Self {
+        self.saturating_add(Point::new(to_i32(x), to_i32(y)))
+    }
+
+    fn saturating_add(self, other: Self) -> Self {
+        Point::new(
+            self.x.saturating_add(other.x),
+            self.y.saturating_add(other.y),
+        )
+    }
+}
+
+/// Converts a size or offset to an `i32`, clamping it to `i32::MAX`.
+pub fn to_i32(value: u32) -> i32 {
+    i32::try_from(value).unwrap_or(i32::MAX)
+}
+
+/// Shrinks `rectangle` so that it ends inside the coordinate space, so that
+/// its corners (and its intersections with other rectangles) can be computed
+/// without overflowing.
+pub fn clamp_rectangle(rectangle: Rectangle) -> Rectangle {
+    let clamp = |start: i32, size: u32| size.min((i32::MAX - start.max(0)) as u32);
+
+    Rectangle::new(
+        rectangle.top_left,
+        Size::new(
+            clamp(rectangle.top_left.x, rectangle.size.width),
+            clamp(rectangle.top_left.y, rectangle.size.height),
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offset() {
+        assert_eq!(Point::new(1, 2).offset(10, 20), Point::new(11, 22));
+        assert_eq!(
+            Point::new(1, -1).offset(u32::MAX, u32::MAX),
+            Point::new(i32::MAX, i32::MAX - 1)
+        );
+        assert_eq!(
+            Point::new(-1, 0).saturating_add(Point::new(i32::MIN, 0)),
+            Point::new(i32::MIN, 0)
+        );
+    }
+
+    #[test]
+    fn clamp_rectangle() {
+        let rectangle = Rectangle::new(Point::new(-5, 5), Size::new(10, 10));
+        assert_eq!(super::clamp_rectangle(rectangle), rectangle);
+
+        let rectangle = Rectangle::new(Point::new(i32::MAX - 5, i32::MIN), Size::new(10, u32::MAX));
+        assert_eq!(
+            super::clamp_rectangle(rectangle),
+            Rectangle::new(rectangle.top_left, Size::new(5, i32::MAX as u32))
+        );
+        assert!(super::clamp_rectangle(rectangle).bottom_right().is_some());
+    }
+}
@@ -0,0 +1,15 @@
+use alloc::vec::Vec;
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+/// A widget whose content didn't fit in the bounds it was given, as reported
+/// by `LayoutNode::overflows`.
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub struct Overflow {
+    /// The indices of the children leading from the root of the layout to the
+    /// widget (empty for the root itself).
+    pub path: Vec<usize>,
+    pub bounds: Rectangle,
+    /// How far the content extends beyond the bounds on each axis.
+    pub amount: Size,
+}
@@ -0,0 +1,22 @@
+/// A single line of wrapped text.
+#[derive(Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub(super) struct Line<'text> {
+    pub text: &'text str,
+    /// Whether the line was cut short by the maximum number of lines, and
+    /// should be followed by an ellipsis.
+    pub ellipsis: bool,
+    /// Whether the line ends a paragraph (or the text). These lines are
+    /// never justified.
+    pub last: bool,
+}
+
+impl<'text> Line<'text> {
+    pub fn new(text: &'text str, last: bool) -> Self {
+        Self {
+            text,
+            ellipsis: false,
+            last,
+        }
+    }
+}
@@ -0,0 +1,470 @@
+mod line;
+mod text_alignment;
+
+pub use text_alignment::TextAlignment;
+
+use super::{
+    offset::Offset,
+    text::{text_width, truncate, ELLIPSIS},
+    IntrinsicSize, LayoutNode, LayoutOptions, Measurement, Widget,
+};
+use alloc::vec::Vec;
+use cherry_macros::Builder;
+use core::{
+    any::Any,
+    hash::{Hash, Hasher},
+};
+use embedded_graphics::{
+    prelude::*,
+    primitives::Rectangle,
+    text::{self, renderer::TextRenderer, Baseline},
+};
+use line::Line;
+
+/// Text that wraps its words to the width it is given, drawn with any of the
+/// text renderers supported by embedded-graphics. Paragraphs are separated by
+/// `\n`. Like `Text`, the text can be borrowed or owned.
+///
+/// A paragraph has no intrinsic width, so it takes the width it is given (by
+/// stretching, growing or a fixed width), and its height follows from the
+/// number of lines the text wraps into at that width.
+#[derive(Clone, Copy, Builder)]
+pub struct Paragraph<Content, Style> {
+    alignment: TextAlignment,
+    #[omit]
+    character_style: Style,
+    layout_options: LayoutOptions,
+    /// The space between lines, in addition to the font's line height.
+    line_spacing: u32,
+    /// The maximum number of lines. Text that doesn't fit is cut short with
+    /// an ellipsis.
+    max_lines: Option<u32>,
+    #[omit]
+    text: Content,
+}
+
+impl<Content, Style> Paragraph<Content, Style>
+where
+    Content: AsRef<str>,
+    Style: TextRenderer + Clone,
+{
+    pub fn new(text: Content, character_style: Style) -> Self {
+        Self {
+            alignment: Default::default(),
+            character_style,
+            layout_options: Default::default(),
+            line_spacing: Default::default(),
+            max_lines: Default::default(),
+            text,
+        }
+    }
+
+    /// The text, which can be changed in place (for example, by writing to a
+    /// `String` without reallocating it).
+    pub fn text_mut(&mut self) -> &mut Content {
+        &mut self.text
+    }
+
+    pub fn set_character_style(&mut self, character_style: Style) {
+        self.character_style = character_style;
+    }
+
+    fn text<'a>(&self, text: &'a str, origin: Point) -> text::Text<'a, Style> {
+        text::Text::with_baseline(text, origin, self.character_style.clone(), Baseline::Top)
+    }
+
+    fn text_width(&self, text: &str) -> u32 {
+        text_width(&self.character_style, text)
+    }
+
+    fn line_width(&self, line: &Line) -> u32 {
+        let ellipsis_width = if line.ellipsis {
+            self.text_width(ELLIPSIS)
+        } else {
+            0
+        };
+
+        self.text_width(line.text).saturating_add(ellipsis_width)
+    }
+
+    fn lines_height(&self, count: usize) -> u32 {
+        let count = count as u32;
+
+        self.character_style
+            .line_height()
+            .saturating_mul(count)
+            .saturating_add(self.line_spacing.saturating_mul(count.saturating_sub(1)))
+    }
+
+    /// Wraps the text to `width`, and cuts it short at the maximum number of
+    /// lines.
+    fn lines(&self, width: u32) -> Vec<Line<'_>> {
+        let mut lines = Vec::new();
+
+        for paragraph in self.text.as_ref().split('\n') {
+            self.wrap_paragraph(paragraph, width, &mut lines);
+        }
+
+        if let Some(max_lines) = self.max_lines {
+            let max_lines = max_lines as usize;
+
+            if lines.len() > max_lines {
+                lines.truncate(max_lines);
+
+                if let Some(line) = lines.last_mut() {
+                    *line = self.ellipsize(line.text, width);
+                }
+            }
+        }
+
+        lines
+    }
+
+    /// Breaks a single paragraph into lines at whitespace. Words that are
+    /// wider than `width` on their own are broken between characters.
+    fn wrap_paragraph<'a>(&self, paragraph: &'a str, width: u32, lines: &mut Vec<Line<'a>>) {
+        // the start and end of the current line, as byte offsets into the
+        // paragraph
+        let mut current: Option<(usize, usize)> = None;
+
+        for word in paragraph.split_whitespace() {
+            let start = word.as_ptr() as usize - paragraph.as_ptr() as usize;
+            let end = start + word.len();
+
+            if let Some((line_start, line_end)) = current {
+                if self.text_width(&paragraph[line_start..end]) <= width {
+                    current = Some((line_start, end));
+                    continue;
+                }
+
+                lines.push(Line::new(&paragraph[line_start..line_end], false));
+            }
+
+            let mut word_start = start;
+
+            loop {
+                let split = word_start + self.fitting_len(&paragraph[word_start..end], width);
+
+                if split == end {
+                    current = Some((word_start, end));
+                    break;
+                }
+
+                lines.push(Line::new(&paragraph[word_start..split], false));
+                word_start = split;
+            }
+        }
+
+        let text = current.map_or("", |(start, end)| &paragraph[start..end]);
+        lines.push(Line::new(text, true));
+    }
+
+    /// The length in bytes of the longest prefix of `text` that fits in
+    /// `width`, which is always at least one character.
+    fn fitting_len(&self, text: &str, width: u32) -> usize {
+        let mut ends = text
+            .char_indices()
+            .map(|(index, character)| index + character.len_utf8());
+        let first = ends.next().unwrap_or(0);
+
+        ends.take_while(|&end| self.text_width(&text[..end]) <= width)
+            .last()
+            .unwrap_or(first)
+    }
+
+    fn ellipsize<'a>(&self, text: &'a str, width: u32) -> Line<'a> {
+        Line {
+            text: truncate(&self.character_style, text, width),
+            ellipsis: true,
+            last: true,
+        }
+    }
+
+    /// The words of a justified line, along with their offsets from the start
+    /// of the line. The space left over is shared between the gaps, with the
+    /// first gaps getting an extra pixel, so that the last word ends at the
+    /// edge.
+    fn justified_words<'a>(&self, text: &'a str, width: u32) -> Vec<(&'a str, u32)> {
+        let words: Vec<&str> = text.split_whitespace().collect();
+        let words_width = words.iter().fold(0, |total: u32, word| {
+            total.saturating_add(self.text_width(word))
+        });
+        let space = width.saturating_sub(words_width);
+        let gaps = (words.len() as u32).saturating_sub(1);
+        let gap = space.checked_div(gaps).unwrap_or(0);
+        let remainder = space.checked_rem(gaps).unwrap_or(0);
+
+        let mut offset: u32 = 0;
+
+        words
+            .into_iter()
+            .enumerate()
+            .map(|(index, word)| {
+                let word_offset = offset;
+                let extra = u32::from((index as u32) < remainder);
+                offset = offset
+                    .saturating_add(self.text_width(word))
+                    .saturating_add(gap)
+                    .saturating_add(extra);
+                (word, word_offset)
+            })
+            .collect()
+    }
+
+    fn draw_line<Display>(
+        &self,
+        display: &mut Display,
+        line: &Line,
+        origin: Point,
+        width: u32,
+    ) -> Result<(), Display::Error>
+    where
+        Display: DrawTarget<Color = Style::Color>,
+    {
+        if self.alignment == TextAlignment::Justified && !line.last {
+            for (word, offset) in self.justified_words(line.text, width) {
+                self.text(word, origin.offset(offset, 0)).draw(display)?;
+            }
+
+            return Ok(());
+        }
+
+        let offset = match self.alignment {
+            TextAlignment::Left | TextAlignment::Justified => 0,
+            TextAlignment::Center => width.saturating_sub(self.line_width(line)) / 2,
+            TextAlignment::Right => width.saturating_sub(self.line_width(line)),
+        };
+        let origin = origin.offset(offset, 0);
+
+        self.text(line.text, origin).draw(display)?;
+
+        if line.ellipsis {
+            let ellipsis_origin = origin.offset(self.text_width(line.text), 0);
+            self.text(ELLIPSIS, ellipsis_origin).draw(display)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<Content, Style, Display> Widget<Display> for Paragraph<Content, Style>
+where
+    Content: AsRef<str>,
+    Style: TextRenderer + Clone,
+    Display: DrawTarget<Color = Style::Color>,
+{
+    fn intrinsic_size(&self) -> IntrinsicSize {
+        let unwrapped_lines = self.lines(u32::MAX).len();
+        IntrinsicSize::new(None, Some(self.lines_height(unwrapped_lines)))
+    }
+
+    fn layout_options(&self) -> LayoutOptions {
+        self.layout_options
+    }
+
+    fn hash_appearance(&self, mut state: &mut dyn Hasher) {
+        self.text.as_ref().hash(&mut state);
+        self.alignment.hash(&mut state);
+        self.line_spacing.hash(&mut state);
+        self.max_lines.hash(&mut state);
+    }
+
+    fn height_for_width(&self, _measurement: &Measurement, width: u32) -> Option<u32> {
+        Some(self.lines_height(self.lines(width).len()))
+    }
+
+    fn arrange(&self, _measurement: &Measurement, bounds: Rectangle) -> LayoutNode {
+        let lines = self.lines(bounds.size.width);
+        let size = Size::new(
+            lines
+                .iter()
+                .map(|line| self.line_width(line))
+                .max()
+                .unwrap_or(0),
+            self.lines_height(lines.len()),
+        );
+
+        LayoutNode::new(bounds)
+            .with_overflow(IntrinsicSize::from(size).overflow(bounds.size))
+            .with_fingerprint(Widget::<Display>::fingerprint(self))
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn draw_clipped(
+        &self,
+        display: &mut Display,
+        layout: &LayoutNode,
+        clip: Rectangle,
+    ) -> Result<(), Display::Error> {
+        let width = layout.size().width;
+        let line_advance = self
+            .character_style
+            .line_height()
+            .saturating_add(self.line_spacing);
+        let area = layout.bounds.intersection(&clip);
+
+        if area.is_zero_sized() {
+            return Ok(());
+        }
+
+        let mut display = display.clipped(&area);
+
+        for (index, line) in self.lines(width).iter().enumerate() {
+            let offset = (index as u32).saturating_mul(line_advance);
+
+            // the lines below the bounds are clipped anyway
+            if offset >= layout.size().height {
+                break;
+            }
+
+            self.draw_line(&mut display, line, layout.origin().offset(0, offset), width)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::{
+        container::{Container, Insets},
+        text::tests::ProportionalStyle,
+    };
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        mono_font::{ascii::FONT_6X10, MonoTextStyle},
+        pixelcolor::{BinaryColor, Rgb888},
+    };
+
+    fn paragraph(text: &str) -> Paragraph<&str, MonoTextStyle<'static, BinaryColor>> {
+        Paragraph::new(text, MonoTextStyle::new(&FONT_6X10, BinaryColor::On))
+    }
+
+    #[test]
+    fn lines() {
+        let paragraph = paragraph("the quick  brown fox\nabcdefghijkl");
+
+        assert_eq!(
+            paragraph.lines(30),
+            vec![
+                Line::new("the", false),
+                Line::new("quick", false),
+                Line::new("brown", false),
+                Line::new("fox", true),
+                Line::new("abcde", false),
+                Line::new("fghij", false),
+                Line::new("kl", true),
+            ]
+        );
+        assert_eq!(
+            paragraph.lines(u32::MAX),
+            vec![
+                Line::new("the quick  brown fox", true),
+                Line::new("abcdefghijkl", true),
+            ]
+        );
+        assert_eq!(paragraph.line_spacing(2).lines_height(7), 82);
+    }
+
+    #[test]
+    fn max_lines() {
+        let paragraph = paragraph("the quick brown fox").max_lines(2);
+
+        assert_eq!(
+            paragraph.lines(30),
+            vec![
+                Line::new("the", false),
+                Line {
+                    text: "qu",
+                    ellipsis: true,
+                    last: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn justified_words() {
+        let paragraph = paragraph("");
+
+        assert_eq!(
+            paragraph.justified_words("a bb c", 31),
+            vec![("a", 0), ("bb", 10), ("c", 25)]
+        );
+        assert_eq!(paragraph.justified_words("a", 31), vec![("a", 0)]);
+    }
+
+    #[test]
+    fn proportional_font() {
+        let paragraph = Paragraph::new("hi hi iiiii", ProportionalStyle);
+
+        assert_eq!(
+            paragraph.lines(12),
+            vec![
+                Line::new("hi", false),
+                Line::new("hi", false),
+                Line::new("iiiii", true),
+            ]
+        );
+    }
+
+    #[test]
+    fn draw() {
+        let bounds = Rectangle::new(Point::zero(), Size::new(40, 30));
+
+        for alignment in [
+            TextAlignment::Left,
+            TextAlignment::Center,
+            TextAlignment::Right,
+            TextAlignment::Justified,
+        ] {
+            let mut display: MockDisplay<BinaryColor> = MockDisplay::new();
+            paragraph("the quick brown fox")
+                .alignment(alignment)
+                .max_lines(3)
+                .draw(&mut display, bounds.top_left, bounds.size)
+                .unwrap();
+
+            let affected_area = display.affected_area();
+            assert_eq!(bounds.intersection(&affected_area), affected_area);
+        }
+    }
+
+    #[test]
+    fn height_for_width_in_container() {
+        let character_style = MonoTextStyle::new(&FONT_6X10, Rgb888::WHITE);
+        let panel = Container::new()
+            .padding(Insets::all(1))
+            .children(vec![
+                Paragraph::new("the quick brown fox", character_style).boxed()
+            ]);
+        let container = Container::<MockDisplay<Rgb888>>::new().children(vec![
+            panel.boxed(),
+            Container::new().width(10).height(10).boxed(),
+        ]);
+
+        let layout = container.layout(Rectangle::new(Point::zero(), Size::new(32, 60)));
+
+        assert_eq!(
+            layout.without_fingerprints().children,
+            vec![
+                LayoutNode::with_children(
+                    Rectangle::new(Point::zero(), Size::new(32, 42)),
+                    vec![LayoutNode::new(Rectangle::new(
+                        Point::new(1, 1),
+                        Size::new(30, 40)
+                    ))]
+                ),
+                LayoutNode::new(Rectangle::new(Point::new(0, 42), Size::new(10, 10))),
+            ]
+        );
+        assert!(layout.overflows().is_empty());
+    }
+}
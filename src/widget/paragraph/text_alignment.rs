@@ -0,0 +1,11 @@
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub enum TextAlignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+    /// Stretches the space between words so that every line except the last
+    /// line of each paragraph fills the width.
+    Justified,
+}
@@ -0,0 +1,15 @@
+/// How a widget is positioned within its parent `Container`, using the `top`,
+/// `left`, `right` and `bottom` offsets in its `LayoutOptions`.
+#[derive(Clone, Copy, Default, Eq, PartialEq)]
+pub enum Position {
+    /// Laid out along the main axis with the other children. The offsets are
+    /// ignored.
+    #[default]
+    Static,
+    /// Laid out along the main axis with the other children, then moved by
+    /// the offsets.
+    Relative,
+    /// Taken out of the main axis flow, and placed using the offsets from the
+    /// edges of the container's content box.
+    Absolute,
+}
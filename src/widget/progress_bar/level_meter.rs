@@ -0,0 +1,156 @@
+use super::{fraction, span};
+use crate::widget::{
+    axis_size::AxisSize,
+    container::{Axis, BoxStyle},
+    IntrinsicSize, LayoutNode, LayoutOptions, Widget,
+};
+use alloc::vec::Vec;
+use cherry_macros::Builder;
+use core::{
+    any::Any,
+    hash::{Hash, Hasher},
+};
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+/// A meter that fills up to its level (such as an audio level), in colors
+/// that change at thresholds. Like a `ProgressBar`, it fills from the left
+/// when it is horizontal and from the bottom when it is vertical, and it has
+/// no intrinsic size.
+#[derive(Clone, Builder)]
+pub struct LevelMeter<Color>
+where
+    Color: PixelColor,
+{
+    axis: Axis,
+    #[omit]
+    color: Color,
+    layout_options: LayoutOptions,
+    level: u32,
+    /// The level that fills the meter.
+    max: u32,
+    /// The colors from each threshold upwards, in increasing order.
+    #[omit]
+    thresholds: Vec<(u32, Color)>,
+    /// The style of the whole meter, behind the filled part.
+    track_style: BoxStyle<Color>,
+}
+
+impl<Color> LevelMeter<Color>
+where
+    Color: PixelColor,
+{
+    pub fn new(color: Color) -> Self {
+        Self {
+            axis: Axis::Horizontal,
+            color,
+            layout_options: Default::default(),
+            level: Default::default(),
+            max: 100,
+            thresholds: Vec::new(),
+            track_style: Default::default(),
+        }
+    }
+
+    /// Draws the part of the meter from `level` upwards in `color` (until the
+    /// next threshold).
+    pub fn threshold(mut self, level: u32, color: Color) -> Self {
+        let index = self
+            .thresholds
+            .partition_point(|(threshold, _)| *threshold <= level);
+        self.thresholds.insert(index, (level, color));
+        self
+    }
+}
+
+impl<Color, Display> Widget<Display> for LevelMeter<Color>
+where
+    Color: 'static + PixelColor,
+    Display: DrawTarget<Color = Color>,
+{
+    fn intrinsic_size(&self) -> IntrinsicSize {
+        IntrinsicSize::none()
+    }
+
+    fn layout_options(&self) -> LayoutOptions {
+        self.layout_options
+    }
+
+    fn hash_appearance(&self, mut state: &mut dyn Hasher) {
+        self.axis.hash(&mut state);
+        self.level.hash(&mut state);
+        self.max.hash(&mut state);
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn draw_clipped(
+        &self,
+        display: &mut Display,
+        layout: &LayoutNode,
+        clip: Rectangle,
+    ) -> Result<(), Display::Error> {
+        self.track_style.draw(display, layout.bounds, clip)?;
+
+        let inner = self.track_style.inner_bounds(layout.bounds);
+        let length = inner.size.for_axis(self.axis);
+        let filled = fraction(length, self.level, self.max);
+
+        // each color covers the range up to the next threshold
+        let starts = core::iter::once(0).chain(
+            self.thresholds
+                .iter()
+                .map(|(threshold, _)| fraction(length, *threshold, self.max)),
+        );
+        let ends = starts.clone().skip(1).chain(core::iter::once(length));
+        let colors =
+            core::iter::once(self.color).chain(self.thresholds.iter().map(|(_, color)| *color));
+
+        for ((start, end), color) in starts.zip(ends).zip(colors) {
+            let end = end.min(filled);
+
+            if start < end {
+                BoxStyle::new().background_color(color).draw(
+                    display,
+                    span(inner, self.axis, start, end),
+                    clip,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::Rgb888};
+
+    #[test]
+    fn thresholds() {
+        let meter = LevelMeter::new(Rgb888::GREEN)
+            .threshold(75, Rgb888::RED)
+            .threshold(50, Rgb888::YELLOW)
+            .max(100);
+
+        let draw = |level| {
+            let mut display = MockDisplay::new();
+            meter
+                .clone()
+                .level(level)
+                .draw(&mut display, Point::zero(), Size::new(4, 1))
+                .unwrap();
+            display
+        };
+
+        draw(100).assert_pattern(&["GGYR"]);
+        draw(60).assert_pattern(&["GG"]);
+        draw(80).assert_pattern(&["GGY"]);
+        draw(0).assert_pattern(&[]);
+    }
+}
@@ -0,0 +1,226 @@
+mod level_meter;
+mod progress_mode;
+
+pub use level_meter::LevelMeter;
+pub use progress_mode::ProgressMode;
+
+use super::{
+    axis_size::AxisSize,
+    container::{Axis, BoxStyle},
+    offset::Offset,
+    IntrinsicSize, LayoutNode, LayoutOptions, Widget,
+};
+use cherry_macros::Builder;
+use core::{
+    any::Any,
+    hash::{Hash, Hasher},
+};
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+/// A bar that shows progress along its axis, filling from the left when it is
+/// horizontal and from the bottom when it is vertical.
+///
+/// The bar has no intrinsic size, so it fills its slot in a `Container` when
+/// it grows and is stretched, or is sized with its layout options.
+#[derive(Clone, Copy, Builder)]
+pub struct ProgressBar<Color>
+where
+    Color: PixelColor,
+{
+    axis: Axis,
+    /// The style of the filled part, which is drawn inside the track's border.
+    bar_style: BoxStyle<Color>,
+    layout_options: LayoutOptions,
+    /// The value that fills the bar.
+    max: u32,
+    mode: ProgressMode,
+    /// The number of pixels an indeterminate bar has moved by. Callers
+    /// advance it from a timer or frame counter, and redraw.
+    tick: u32,
+    /// The style of the whole bar, behind the filled part.
+    track_style: BoxStyle<Color>,
+    value: u32,
+}
+
+impl<Color> ProgressBar<Color>
+where
+    Color: PixelColor,
+{
+    pub fn new(bar_color: Color) -> Self {
+        Self {
+            axis: Axis::Horizontal,
+            bar_style: BoxStyle::new().background_color(bar_color),
+            layout_options: Default::default(),
+            max: 100,
+            mode: Default::default(),
+            tick: Default::default(),
+            track_style: Default::default(),
+            value: Default::default(),
+        }
+    }
+
+    /// The start and end of each filled part of the bar, along an axis that
+    /// is `length` long.
+    fn filled_ranges(&self, length: u32, mut f: impl FnMut(u32, u32)) {
+        match self.mode {
+            ProgressMode::Fixed => f(0, fraction(length, self.value, self.max)),
+            ProgressMode::Indeterminate { length: block } => {
+                // the block enters at the start and leaves at the end
+                let period = length.saturating_add(block);
+                let end = self.tick.checked_rem(period).unwrap_or(0);
+                f(end.saturating_sub(block), end.min(length));
+            }
+            ProgressMode::Segmented { count, gap } => {
+                let filled = fraction(count, self.value, self.max);
+                let total = length.saturating_add(gap) as u64;
+
+                for segment in 0..filled as u64 {
+                    let start = segment * total / count as u64;
+                    let end = ((segment + 1) * total / count as u64).saturating_sub(gap as u64);
+                    f(start as u32, end as u32);
+                }
+            }
+        }
+    }
+}
+
+impl<Color, Display> Widget<Display> for ProgressBar<Color>
+where
+    Color: 'static + PixelColor,
+    Display: DrawTarget<Color = Color>,
+{
+    fn intrinsic_size(&self) -> IntrinsicSize {
+        IntrinsicSize::none()
+    }
+
+    fn layout_options(&self) -> LayoutOptions {
+        self.layout_options
+    }
+
+    fn hash_appearance(&self, mut state: &mut dyn Hasher) {
+        self.axis.hash(&mut state);
+        self.max.hash(&mut state);
+        self.mode.hash(&mut state);
+        self.tick.hash(&mut state);
+        self.value.hash(&mut state);
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn draw_clipped(
+        &self,
+        display: &mut Display,
+        layout: &LayoutNode,
+        clip: Rectangle,
+    ) -> Result<(), Display::Error> {
+        self.track_style.draw(display, layout.bounds, clip)?;
+
+        let inner = self.track_style.inner_bounds(layout.bounds);
+        let mut result = Ok(());
+
+        self.filled_ranges(inner.size.for_axis(self.axis), |start, end| {
+            if result.is_ok() && start < end {
+                let bar = span(inner, self.axis, start, end);
+                result = self.bar_style.draw(display, bar, clip);
+            }
+        });
+
+        result
+    }
+}
+
+/// `length` scaled by `value / max`, where values above `max` count as `max`.
+fn fraction(length: u32, value: u32, max: u32) -> u32 {
+    let value = value.min(max) as u64;
+    (length as u64 * value).checked_div(max as u64).unwrap_or(0) as u32
+}
+
+/// The part of `bounds` from `start` to `end` along `axis`, measured from the
+/// left when the axis is horizontal, and from the bottom when it is vertical.
+fn span(bounds: Rectangle, axis: Axis, start: u32, end: u32) -> Rectangle {
+    let length = end.saturating_sub(start);
+
+    match axis {
+        Axis::Horizontal => Rectangle::new(
+            bounds.top_left.offset(start, 0),
+            Size::new(length, bounds.size.height),
+        ),
+        Axis::Vertical => Rectangle::new(
+            bounds
+                .top_left
+                .offset(0, bounds.size.height.saturating_sub(end)),
+            Size::new(bounds.size.width, length),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::container::{Border, Container};
+    use alloc::vec;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::BinaryColor};
+
+    fn draw(progress_bar: ProgressBar<BinaryColor>, size: Size) -> MockDisplay<BinaryColor> {
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        progress_bar
+            .draw(&mut display, Point::zero(), size)
+            .unwrap();
+        display
+    }
+
+    #[test]
+    fn fixed() {
+        let progress_bar = ProgressBar::new(BinaryColor::On).value(50);
+
+        draw(progress_bar, Size::new(4, 1)).assert_pattern(&["##"]);
+        draw(progress_bar.axis(Axis::Vertical), Size::new(1, 4))
+            .assert_pattern(&[" ", " ", "#", "#"]);
+        draw(
+            progress_bar.track_style(BoxStyle::new().border(Border {
+                color: BinaryColor::Off,
+                width: 1,
+            })),
+            Size::new(6, 3),
+        )
+        .assert_pattern(&["......", ".##  .", "......"]);
+    }
+
+    #[test]
+    fn indeterminate() {
+        let progress_bar =
+            ProgressBar::new(BinaryColor::On).mode(ProgressMode::Indeterminate { length: 2 });
+
+        draw(progress_bar, Size::new(4, 1)).assert_pattern(&[]);
+        draw(progress_bar.tick(1), Size::new(4, 1)).assert_pattern(&["#"]);
+        draw(progress_bar.tick(3), Size::new(4, 1)).assert_pattern(&[" ##"]);
+        draw(progress_bar.tick(5), Size::new(4, 1)).assert_pattern(&["   #"]);
+    }
+
+    #[test]
+    fn segmented() {
+        let progress_bar = ProgressBar::new(BinaryColor::On)
+            .mode(ProgressMode::Segmented { count: 3, gap: 1 })
+            .value(70);
+
+        draw(progress_bar, Size::new(8, 1)).assert_pattern(&["## ##"]);
+    }
+
+    #[test]
+    fn fills_slot() {
+        let container = Container::<MockDisplay<BinaryColor>>::new()
+            .axis(Axis::Horizontal)
+            .children(vec![ProgressBar::new(BinaryColor::On)
+                .layout_options(LayoutOptions::new().grow(1))
+                .boxed()]);
+
+        let layout = container.layout(Rectangle::new(Point::zero(), Size::new(20, 10)));
+        assert_eq!(layout.children[0].bounds, layout.bounds);
+    }
+}
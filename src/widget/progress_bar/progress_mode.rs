@@ -0,0 +1,14 @@
+/// How a `ProgressBar` shows its value.
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub enum ProgressMode {
+    /// The bar is filled in proportion to the value.
+    #[default]
+    Fixed,
+    /// A block `length` pixels long moves along the bar as the tick advances,
+    /// for progress that can't be measured. The value is ignored.
+    Indeterminate { length: u32 },
+    /// The bar is divided into `count` segments, `gap` pixels apart, and only
+    /// whole segments are filled.
+    Segmented { count: u32, gap: u32 },
+}
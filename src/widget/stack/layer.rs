@@ -0,0 +1,32 @@
+use crate::widget::{container::Alignment, Widget};
+use alloc::boxed::Box;
+use cherry_macros::Builder;
+use embedded_graphics::prelude::*;
+
+/// A child of a `Stack`, along with how it is placed within the stack.
+#[derive(Builder)]
+pub struct Layer<Display>
+where
+    Display: DrawTarget,
+{
+    #[omit]
+    pub(super) child: Box<dyn Widget<Display>>,
+    pub(super) horizontal_alignment: Option<Alignment>,
+    /// Moves the child after it has been aligned.
+    pub(super) offset: Point,
+    pub(super) vertical_alignment: Option<Alignment>,
+}
+
+impl<Display> Layer<Display>
+where
+    Display: DrawTarget,
+{
+    pub fn new(child: Box<dyn Widget<Display>>) -> Self {
+        Self {
+            child,
+            horizontal_alignment: Default::default(),
+            offset: Point::zero(),
+            vertical_alignment: Default::default(),
+        }
+    }
+}
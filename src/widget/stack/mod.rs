@@ -0,0 +1,202 @@
+mod layer;
+
+pub use layer::Layer;
+
+use super::{
+    container::{Alignment, Axis},
+    offset::Offset,
+    IntrinsicSize, LayoutNode, LayoutOptions, Measurement, Widget,
+};
+use alloc::vec::Vec;
+use cherry_macros::Builder;
+use core::any::Any;
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+/// Draws its children in the same rectangle, each one on top of the previous
+/// ones. As in a `Grid`, `Alignment::Stretch` fills the whole rectangle.
+#[derive(Builder)]
+pub struct Stack<Display>
+where
+    Display: DrawTarget,
+{
+    horizontal_alignment: Alignment,
+    layers: Vec<Layer<Display>>,
+    layout_options: LayoutOptions,
+    vertical_alignment: Alignment,
+}
+
+impl<Display> Default for Stack<Display>
+where
+    Display: DrawTarget,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Display> Stack<Display>
+where
+    Display: DrawTarget,
+{
+    pub fn new() -> Self {
+        Self {
+            horizontal_alignment: Default::default(),
+            layers: Default::default(),
+            layout_options: Default::default(),
+            vertical_alignment: Default::default(),
+        }
+    }
+}
+
+impl<Display> Widget<Display> for Stack<Display>
+where
+    Display: DrawTarget,
+{
+    fn intrinsic_size(&self) -> IntrinsicSize {
+        self.measure().intrinsic_size
+    }
+
+    fn layout_options(&self) -> LayoutOptions {
+        self.layout_options
+    }
+
+    fn measure(&self) -> Measurement {
+        self.measure_with_children(
+            self.layers
+                .iter()
+                .map(|layer| layer.child.measure())
+                .collect(),
+        )
+    }
+
+    fn measure_with_children(&self, children: Vec<Measurement>) -> Measurement {
+        let intrinsic_size = self
+            .layers
+            .iter()
+            .zip(&children)
+            .map(|(layer, measurement)| {
+                layer
+                    .child
+                    .layout_options()
+                    .resolve_intrinsic_size(measurement.intrinsic_size, None)
+            })
+            .fold(IntrinsicSize::none(), |size, child_size| {
+                let max = |a: Option<u32>, b: Option<u32>| match (a, b) {
+                    (Some(a), Some(b)) => Some(a.max(b)),
+                    (a, b) => a.or(b),
+                };
+
+                IntrinsicSize::new(
+                    max(size.width, child_size.width),
+                    max(size.height, child_size.height),
+                )
+            });
+
+        Measurement::with_children(
+            self.layout_options.clamp_intrinsic_size(intrinsic_size),
+            children,
+        )
+    }
+
+    fn arrange(&self, measurement: &Measurement, bounds: Rectangle) -> LayoutNode {
+        let nodes = self
+            .layers
+            .iter()
+            .zip(&measurement.children)
+            .map(|(layer, measurement)| {
+                let layout_options = layer.child.layout_options();
+                let intrinsic_size = layout_options
+                    .resolve_intrinsic_size(measurement.intrinsic_size, Some(bounds.size));
+
+                let (x, width) = layer
+                    .horizontal_alignment
+                    .unwrap_or(self.horizontal_alignment)
+                    .fill(
+                        bounds.size.width,
+                        intrinsic_size.width,
+                        &layout_options,
+                        Axis::Horizontal,
+                    );
+                let (y, height) = layer
+                    .vertical_alignment
+                    .unwrap_or(self.vertical_alignment)
+                    .fill(
+                        bounds.size.height,
+                        intrinsic_size.height,
+                        &layout_options,
+                        Axis::Vertical,
+                    );
+
+                let origin = bounds.top_left.offset(x, y).saturating_add(layer.offset);
+                layer.child.arrange(
+                    measurement,
+                    Rectangle::new(origin, Size::new(width, height)),
+                )
+            })
+            .collect();
+
+        let node = LayoutNode::with_children(bounds, nodes);
+        let overflow = node
+            .children_overflow(bounds)
+            .component_max(measurement.intrinsic_size.overflow(bounds.size));
+        node.with_overflow(overflow)
+            .with_fingerprint(self.fingerprint())
+    }
+
+    fn child_mut(&mut self, index: usize) -> Option<&mut (dyn Widget<Display> + 'static)> {
+        self.layers.get_mut(index).map(|layer| layer.child.as_mut())
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn draw_clipped(
+        &self,
+        display: &mut Display,
+        layout: &LayoutNode,
+        clip: Rectangle,
+    ) -> Result<(), Display::Error> {
+        for (layer, child_layout) in self.layers.iter().zip(&layout.children) {
+            layer.child.draw_clipped(display, child_layout, clip)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::container::Container;
+    use embedded_graphics::{mock_display::MockDisplay, pixelcolor::Rgb888};
+
+    #[test]
+    fn layout() {
+        let stack = Stack::<MockDisplay<Rgb888>>::new().layers(vec![
+            Layer::new(Container::new().width(30).height(20).boxed()),
+            Layer::new(Container::new().width(6).height(6).boxed())
+                .horizontal_alignment(Alignment::End)
+                .vertical_alignment(Alignment::Start)
+                .offset(Point::new(2, -2)),
+        ]);
+
+        assert_eq!(
+            stack.intrinsic_size(),
+            IntrinsicSize::new(Some(30), Some(20))
+        );
+
+        let layout = stack.layout(Rectangle::new(Point::new(10, 10), Size::new(30, 20)));
+
+        assert_eq!(
+            layout.without_fingerprints().children,
+            vec![
+                LayoutNode::new(Rectangle::new(Point::new(10, 10), Size::new(30, 20))),
+                LayoutNode::new(Rectangle::new(Point::new(36, 8), Size::new(6, 6))),
+            ]
+        );
+    }
+}
@@ -0,0 +1,516 @@
+mod text_overflow;
+
+pub use text_overflow::TextOverflow;
+
+use super::{
+    container::Alignment,
+    offset::{to_i32, Offset},
+    IntrinsicSize, LayoutNode, LayoutOptions, Measurement, Widget,
+};
+use alloc::string::String;
+use cherry_macros::Builder;
+use core::{
+    any::Any,
+    fmt,
+    hash::{Hash, Hasher},
+};
+use embedded_graphics::{
+    prelude::*,
+    primitives::Rectangle,
+    text::{self, renderer::TextRenderer, Baseline},
+};
+
+pub(crate) const ELLIPSIS: &str = "...";
+
+pub(crate) fn text_width<Style>(character_style: &Style, text: &str) -> u32
+where
+    Style: TextRenderer,
+{
+    character_style
+        .measure_string(text, Point::zero(), Baseline::Top)
+        .bounding_box
+        .size
+        .width
+}
+
+/// Shortens `text` at a character boundary until it fits in `width` when
+/// followed by an ellipsis.
+pub(crate) fn truncate<'text, Style>(
+    character_style: &Style,
+    mut text: &'text str,
+    width: u32,
+) -> &'text str
+where
+    Style: TextRenderer,
+{
+    let available = width.saturating_sub(text_width(character_style, ELLIPSIS));
+
+    while !text.is_empty() && text_width(character_style, text) > available {
+        let mut characters = text.chars();
+        characters.next_back();
+        text = characters.as_str();
+    }
+
+    text.trim_end()
+}
+
+/// A single line of text, drawn with any of the text renderers supported by
+/// embedded-graphics (such as `MonoTextStyle`).
+///
+/// The text can be borrowed (`&str`) or owned by the widget (such as an
+/// `alloc::string::String` or a `heapless::String<N>`), so that labels showing
+/// live values can be boxed into a widget tree.
+///
+/// When the text is given more room than it needs, it is placed according to
+/// its alignments (where `Stretch` behaves like `Start`).
+#[derive(Clone, Copy, Builder)]
+pub struct Text<Content, Style> {
+    /// The line of the text that is placed at the top, middle or bottom of its
+    /// bounds by the vertical alignment. By default, this is the top, middle
+    /// or bottom of the text, to match the alignment. `Baseline::Alphabetic`
+    /// lines up text in different fonts, with descenders hanging below.
+    baseline: Option<Baseline>,
+    #[omit]
+    character_style: Style,
+    horizontal_alignment: Alignment,
+    layout_options: LayoutOptions,
+    overflow: TextOverflow,
+    #[omit]
+    text: Content,
+    /// The number of pixels a marquee has scrolled by. Callers advance it
+    /// from a timer or frame counter, and redraw.
+    tick: u32,
+    vertical_alignment: Alignment,
+}
+
+impl<Style> Text<String, Style>
+where
+    Style: TextRenderer + Clone,
+{
+    /// Creates text that owns the result of formatting `arguments`, such as
+    /// `Text::format(format_args!("{}°C", temperature), character_style)`.
+    pub fn format(arguments: fmt::Arguments, character_style: Style) -> Self {
+        Self::new(alloc::fmt::format(arguments), character_style)
+    }
+}
+
+impl<Content, Style> Text<Content, Style>
+where
+    Content: AsRef<str>,
+    Style: TextRenderer + Clone,
+{
+    pub fn new(text: Content, character_style: Style) -> Self {
+        Self {
+            baseline: Default::default(),
+            character_style,
+            horizontal_alignment: Default::default(),
+            layout_options: Default::default(),
+            overflow: Default::default(),
+            text,
+            tick: Default::default(),
+            vertical_alignment: Default::default(),
+        }
+    }
+
+    /// The text, which can be changed in place (for example, by writing to a
+    /// `String` without reallocating it).
+    pub fn text_mut(&mut self) -> &mut Content {
+        &mut self.text
+    }
+
+    pub fn set_character_style(&mut self, character_style: Style) {
+        self.character_style = character_style;
+    }
+
+    fn text<'a>(
+        &self,
+        text: &'a str,
+        position: Point,
+        baseline: Baseline,
+    ) -> text::Text<'a, Style> {
+        text::Text::with_baseline(text, position, self.character_style.clone(), baseline)
+    }
+
+    /// Where to draw text that is `width` wide in `bounds`, along with the
+    /// baseline the position refers to.
+    fn position(&self, bounds: Rectangle, width: u32) -> (Point, Baseline) {
+        let unused_width = bounds.size.width.saturating_sub(width);
+        let x = match self.horizontal_alignment {
+            Alignment::Stretch | Alignment::Start => 0,
+            Alignment::Center => unused_width / 2,
+            Alignment::End => unused_width,
+        };
+
+        let last_row = bounds.size.height.saturating_sub(1);
+        let (y, baseline) = match self.vertical_alignment {
+            Alignment::Stretch | Alignment::Start => (0, Baseline::Top),
+            Alignment::Center => (last_row / 2, Baseline::Middle),
+            Alignment::End => (last_row, Baseline::Bottom),
+        };
+
+        (
+            bounds.top_left.offset(x, y),
+            self.baseline.unwrap_or(baseline),
+        )
+    }
+
+    /// How far a marquee with `gap` between repetitions has scrolled. It wraps
+    /// around once the text and the gap have scrolled past.
+    fn marquee_offset(&self, gap: u32) -> u32 {
+        let period = text_width(&self.character_style, self.text.as_ref()).saturating_add(gap);
+        self.tick.checked_rem(period).unwrap_or(0)
+    }
+}
+
+impl<Content, Style, Display> Widget<Display> for Text<Content, Style>
+where
+    Content: AsRef<str>,
+    Style: TextRenderer + Clone,
+    Display: DrawTarget<Color = Style::Color>,
+{
+    fn intrinsic_size(&self) -> IntrinsicSize {
+        self.text(self.text.as_ref(), Point::zero(), Baseline::Top)
+            .bounding_box()
+            .size
+            .into()
+    }
+
+    fn layout_options(&self) -> LayoutOptions {
+        self.layout_options
+    }
+
+    fn hash_appearance(&self, mut state: &mut dyn Hasher) {
+        self.text.as_ref().hash(&mut state);
+        self.baseline.hash(&mut state);
+        self.horizontal_alignment.hash(&mut state);
+        self.overflow.hash(&mut state);
+        self.tick.hash(&mut state);
+        self.vertical_alignment.hash(&mut state);
+    }
+
+    /// Text that is truncated or scrolled never overflows horizontally.
+    fn arrange(&self, measurement: &Measurement, bounds: Rectangle) -> LayoutNode {
+        let mut overflow = measurement.intrinsic_size.overflow(bounds.size);
+
+        if self.overflow != TextOverflow::Clip {
+            overflow.width = 0;
+        }
+
+        LayoutNode::new(bounds)
+            .with_overflow(overflow)
+            .with_fingerprint(Widget::<Display>::fingerprint(self))
+    }
+
+    fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+    where
+        Self: 'static,
+    {
+        Some(self)
+    }
+
+    fn draw_clipped(
+        &self,
+        display: &mut Display,
+        layout: &LayoutNode,
+        clip: Rectangle,
+    ) -> Result<(), Display::Error> {
+        let width = layout.size().width;
+        let full_width = text_width(&self.character_style, self.text.as_ref());
+        let area = layout.bounds.intersection(&clip);
+
+        if area.is_zero_sized() {
+            return Ok(());
+        }
+
+        let mut display = display.clipped(&area);
+
+        match self.overflow {
+            TextOverflow::Ellipsis if full_width > width => {
+                let text = truncate(&self.character_style, self.text.as_ref(), width);
+                let ellipsis_offset = text_width(&self.character_style, text);
+                let truncated_width =
+                    ellipsis_offset.saturating_add(text_width(&self.character_style, ELLIPSIS));
+                let (position, baseline) = self.position(layout.bounds, truncated_width);
+
+                self.text(text, position, baseline).draw(&mut display)?;
+                self.text(ELLIPSIS, position.offset(ellipsis_offset, 0), baseline)
+                    .draw(&mut display)?;
+            }
+            TextOverflow::Marquee { gap } if full_width > width => {
+                let (position, baseline) = self.position(layout.bounds, full_width);
+                let offset = self.marquee_offset(gap);
+                let period = full_width.saturating_add(gap);
+
+                // the text, followed by the start of its next repetition once
+                // it has scrolled into view
+                self.text(
+                    self.text.as_ref(),
+                    position.saturating_add(Point::new(-to_i32(offset), 0)),
+                    baseline,
+                )
+                .draw(&mut display)?;
+
+                if period - offset < width {
+                    self.text(
+                        self.text.as_ref(),
+                        position.offset(period - offset, 0),
+                        baseline,
+                    )
+                    .draw(&mut display)?;
+                }
+            }
+            _ => {
+                let (position, baseline) = self.position(layout.bounds, full_width);
+                self.text(self.text.as_ref(), position, baseline)
+                    .draw(&mut display)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::widget::{container::Container, LayoutNode};
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        mono_font::{ascii::FONT_6X10, MonoTextStyle},
+        pixelcolor::BinaryColor,
+        text::renderer::TextMetrics,
+    };
+
+    /// A text renderer with a proportional font, where `i` is narrower than
+    /// every other character. Nothing is drawn.
+    #[derive(Clone, Copy)]
+    pub(crate) struct ProportionalStyle;
+
+    impl ProportionalStyle {
+        fn width(&self, text: &str) -> u32 {
+            text.chars()
+                .map(|character| if character == 'i' { 2 } else { 5 })
+                .sum()
+        }
+    }
+
+    impl TextRenderer for ProportionalStyle {
+        type Color = BinaryColor;
+
+        fn draw_string<D>(
+            &self,
+            text: &str,
+            position: Point,
+            _baseline: Baseline,
+            _target: &mut D,
+        ) -> Result<Point, D::Error>
+        where
+            D: DrawTarget<Color = Self::Color>,
+        {
+            Ok(position + Point::new(self.width(text) as i32, 0))
+        }
+
+        fn draw_whitespace<D>(
+            &self,
+            width: u32,
+            position: Point,
+            _baseline: Baseline,
+            _target: &mut D,
+        ) -> Result<Point, D::Error>
+        where
+            D: DrawTarget<Color = Self::Color>,
+        {
+            Ok(position + Point::new(width as i32, 0))
+        }
+
+        fn measure_string(&self, text: &str, position: Point, _baseline: Baseline) -> TextMetrics {
+            let size = Size::new(self.width(text), self.line_height());
+
+            TextMetrics {
+                bounding_box: Rectangle::new(position, size),
+                next_position: position + size.x_axis(),
+            }
+        }
+
+        fn line_height(&self) -> u32 {
+            8
+        }
+    }
+
+    #[test]
+    fn proportional_font() {
+        let container = Container::<MockDisplay<BinaryColor>>::new().children(vec![
+            Text::new("hi", ProportionalStyle).boxed(),
+            Text::new("iii", ProportionalStyle).boxed(),
+        ]);
+
+        let layout = container.layout(Rectangle::new(Point::zero(), Size::new(20, 20)));
+
+        assert_eq!(
+            layout.without_fingerprints().children,
+            vec![
+                LayoutNode::new(Rectangle::new(Point::zero(), Size::new(7, 8))),
+                LayoutNode::new(Rectangle::new(Point::new(0, 8), Size::new(6, 8))),
+            ]
+        );
+    }
+
+    #[test]
+    fn truncate() {
+        let character_style = MonoTextStyle::new(&FONT_6X10, BinaryColor::On);
+
+        assert_eq!(super::truncate(&character_style, "hello world", 40), "hel");
+        assert_eq!(
+            super::truncate(&character_style, "hello world", 54),
+            "hello"
+        );
+        assert_eq!(super::truncate(&character_style, "hello world", 10), "");
+    }
+
+    #[test]
+    fn overflow() {
+        let character_style = MonoTextStyle::new(&FONT_6X10, BinaryColor::On);
+        let bounds = Rectangle::new(Point::zero(), Size::new(40, 10));
+        let text = Text::new("hello world", character_style);
+
+        let layout = Widget::<MockDisplay<BinaryColor>>::layout(&text, bounds);
+        assert_eq!(layout.overflow, Size::new(26, 0));
+
+        for overflow in [TextOverflow::Ellipsis, TextOverflow::Marquee { gap: 10 }] {
+            let text = text.overflow(overflow).tick(80);
+            let mut display = MockDisplay::new();
+            let layout = Widget::<MockDisplay<BinaryColor>>::layout(&text, bounds);
+            text.draw_layout(&mut display, &layout).unwrap();
+
+            assert_eq!(layout.overflow, Size::zero());
+            assert_eq!(
+                bounds.intersection(&display.affected_area()),
+                display.affected_area()
+            );
+        }
+    }
+
+    #[test]
+    fn marquee_offset() {
+        let character_style = MonoTextStyle::new(&FONT_6X10, BinaryColor::On);
+        let text = Text::new("hello world", character_style);
+
+        assert_eq!(text.tick(30).marquee_offset(10), 30);
+        assert_eq!(text.tick(80).marquee_offset(10), 4);
+    }
+
+    #[test]
+    fn position() {
+        let character_style = MonoTextStyle::new(&FONT_6X10, BinaryColor::On);
+        let bounds = Rectangle::new(Point::new(2, 2), Size::new(40, 30));
+        let text = Text::new("hi", character_style);
+
+        assert_eq!(text.position(bounds, 12), (Point::new(2, 2), Baseline::Top));
+
+        let centered = text
+            .horizontal_alignment(Alignment::Center)
+            .vertical_alignment(Alignment::Center);
+        let (position, baseline) = centered.position(bounds, 12);
+        assert_eq!((position, baseline), (Point::new(16, 16), Baseline::Middle));
+        assert_eq!(
+            centered.text("hi", position, baseline).bounding_box(),
+            Rectangle::new(Point::new(16, 12), Size::new(12, 10))
+        );
+
+        let baseline_aligned = text
+            .horizontal_alignment(Alignment::End)
+            .vertical_alignment(Alignment::End)
+            .baseline(Baseline::Alphabetic);
+        assert_eq!(
+            baseline_aligned.position(bounds, 12),
+            (Point::new(30, 31), Baseline::Alphabetic)
+        );
+    }
+
+    #[test]
+    fn owned_text() {
+        let character_style = MonoTextStyle::new(&FONT_6X10, BinaryColor::On);
+        let temperature = 21;
+
+        let container = Container::<MockDisplay<BinaryColor>>::new().children(vec![
+            Text::format(format_args!("{} C", temperature), character_style).boxed(),
+            Text::new(String::from("off"), character_style).boxed(),
+        ]);
+
+        assert_eq!(
+            container.intrinsic_size(),
+            IntrinsicSize::new(Some(24), Some(20))
+        );
+    }
+
+    #[test]
+    fn draw_regions() {
+        let character_style = MonoTextStyle::new(&FONT_6X10, BinaryColor::On);
+        let frame = |temperature: u32| {
+            Container::<MockDisplay<BinaryColor>>::new().children(vec![
+                Text::new("temp", character_style).boxed(),
+                Text::format(format_args!("{} C", temperature), character_style).boxed(),
+            ])
+        };
+        let bounds = Rectangle::new(Point::zero(), Size::new(30, 20));
+
+        let (before, after) = (frame(20), frame(21));
+        let previous = before.layout(bounds);
+        let layout = after.layout(bounds);
+
+        let regions = layout.dirty_regions(&previous);
+        assert_eq!(regions.rectangles(), &[layout.children[1].bounds]);
+        assert!(layout.dirty_regions(&after.layout(bounds)).is_empty());
+
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        before.draw_layout(&mut display, &previous).unwrap();
+        after
+            .draw_regions(&mut display, &layout, &regions, BinaryColor::Off)
+            .unwrap();
+
+        let mut expected = MockDisplay::new();
+        expected.set_allow_overdraw(true);
+        expected
+            .fill_solid(&regions.rectangles()[0], BinaryColor::Off)
+            .unwrap();
+        after.draw_layout(&mut expected, &layout).unwrap();
+        display.assert_eq(&expected);
+    }
+
+    #[test]
+    fn draw_at_edge() {
+        let character_style = MonoTextStyle::new(&FONT_6X10, BinaryColor::On);
+        let text = Text::new("hi", character_style);
+        let edge = LayoutNode::new(Rectangle::new(
+            Point::new(i32::MAX - 40, i32::MAX - 10),
+            Size::new(40, 10),
+        ));
+
+        for overflow in [
+            TextOverflow::Clip,
+            TextOverflow::Ellipsis,
+            TextOverflow::Marquee { gap: u32::MAX },
+        ] {
+            for alignment in [Alignment::Start, Alignment::End] {
+                let text = text
+                    .overflow(overflow)
+                    .tick(3)
+                    .horizontal_alignment(alignment)
+                    .vertical_alignment(alignment);
+                let mut display = MockDisplay::new();
+                text.draw_layout(&mut display, &edge).unwrap();
+            }
+        }
+
+        // the next repetition of a marquee with the widest gap stays out of view
+        let layout = LayoutNode::new(Rectangle::new(Point::zero(), Size::new(6, 10)));
+        let mut display = MockDisplay::new();
+        text.overflow(TextOverflow::Marquee { gap: u32::MAX })
+            .draw_layout(&mut display, &layout)
+            .unwrap();
+        let mut expected = MockDisplay::new();
+        text.draw_layout(&mut expected, &layout).unwrap();
+        display.assert_eq(&expected);
+    }
+}
@@ -0,0 +1,13 @@
+/// How text that is wider than its bounds is drawn.
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+#[cfg_attr(test, derive(Debug))]
+pub enum TextOverflow {
+    /// Cuts the text off at the edge of the bounds.
+    #[default]
+    Clip,
+    /// Cuts the text at a character boundary, followed by "...".
+    Ellipsis,
+    /// Scrolls the text horizontally by one pixel per tick, with `gap` pixels
+    /// between the end of the text and the start of the next repetition.
+    Marquee { gap: u32 },
+}
@@ -0,0 +1,746 @@
+use super::{
+    Direction, DirtyRegions, Event, IntrinsicSize, Key, LayoutNode, LayoutOptions, Measurement,
+    Message, Widget,
+};
+use alloc::{boxed::Box, vec::Vec};
+use core::mem;
+use embedded_graphics::{prelude::*, primitives::Rectangle};
+
+/// A widget tree that is kept between frames, along with its measurement and
+/// layout. Widgets with a `key` in their layout options can be updated in
+/// place, after which only the parts of the layout that depend on them are
+/// recomputed, and only the regions that changed are redrawn.
+///
+/// The tree also keeps track of which focusable widget has focus. The focus
+/// order is the order of the widgets in the tree, so it follows the order of
+/// the children of each `Container`.
+pub struct Tree<Display>
+where
+    Display: DrawTarget,
+{
+    bounds: Rectangle,
+    dirty_regions: DirtyRegions,
+    /// The position of the focused widget in the focus order.
+    focus: Option<usize>,
+    layout: LayoutNode,
+    measurement: Measurement,
+    root: Box<dyn Widget<Display>>,
+}
+
+impl<Display> Tree<Display>
+where
+    Display: DrawTarget,
+{
+    pub fn new(root: Box<dyn Widget<Display>>, bounds: Rectangle) -> Self {
+        let measurement = root.measure();
+        let layout = root.arrange(&measurement, bounds);
+
+        // the first draw draws everything
+        let mut dirty_regions = DirtyRegions::new();
+        dirty_regions.add(bounds);
+
+        Self {
+            bounds,
+            dirty_regions,
+            focus: None,
+            layout,
+            measurement,
+            root,
+        }
+    }
+
+    pub fn root(&self) -> &dyn Widget<Display> {
+        self.root.as_ref()
+    }
+
+    pub fn layout(&self) -> &LayoutNode {
+        &self.layout
+    }
+
+    /// The regions that the next `draw` will redraw.
+    pub fn dirty_regions(&self) -> &DirtyRegions {
+        &self.dirty_regions
+    }
+
+    /// Calls `update` with the widget with `key`, and recomputes the layout
+    /// of the widget. If the update changed the size or layout options of
+    /// the widget, the layout of its parent is recomputed as well, and so on
+    /// up the tree. The widget is always redrawn by the next `draw`, since
+    /// the update may have changed parts of its appearance (such as colors)
+    /// that its fingerprint doesn't cover. The focus stays with the focused
+    /// widget, and is cleared if the update made it unfocusable. Returns
+    /// `false` (without calling `update`) if there is no widget of type `W`
+    /// with `key`.
+    pub fn update<W, F>(&mut self, key: Key, update: F) -> bool
+    where
+        Display: 'static,
+        W: Widget<Display> + 'static,
+        F: FnOnce(&mut W),
+    {
+        let mut path = Vec::new();
+
+        if !find_key(self.root.as_mut(), &self.layout, key, &mut path) {
+            return false;
+        }
+
+        let result = update_widget(
+            self.root.as_mut(),
+            &mut self.measurement,
+            &mut self.layout,
+            &path,
+            update,
+            &mut self.dirty_regions,
+        );
+
+        match result {
+            Update::NotFound => return false,
+            Update::Settled => {}
+            Update::Changed => {
+                // the root always has the bounds of the tree
+                let layout = self.root.arrange(&self.measurement, self.bounds);
+                layout.collect_dirty_regions(&self.layout, &mut self.dirty_regions);
+                self.layout = layout;
+            }
+        }
+
+        self.refresh_focus();
+        true
+    }
+
+    /// Sends `event` through the tree, and returns the message it produced,
+    /// if any. Widgets whose appearance changed in response are redrawn by
+    /// the next `draw`, but changes of size need an `update`.
+    ///
+    /// When the tree has focusable widgets, the arrow keys move the focus
+    /// with `move_focus`, encoders move it with `step_focus`, and other keys
+    /// are sent to the focused widget only.
+    pub fn event(&mut self, event: &Event) -> Option<Message> {
+        let message = match *event {
+            Event::Key(key_code) if self.has_focusable() => {
+                match Direction::from_key_code(key_code) {
+                    Some(direction) => {
+                        self.move_focus(direction);
+                        None
+                    }
+                    None => self.focused_event(event),
+                }
+            }
+            Event::Encoder(steps) if self.has_focusable() => {
+                self.step_focus(steps);
+                None
+            }
+            _ => self.root.event(event, &self.layout),
+        };
+
+        refresh_fingerprints(
+            self.root.as_mut(),
+            &mut self.layout,
+            &mut self.dirty_regions,
+        );
+        message
+    }
+
+    /// The position of the focused widget in the focus order.
+    pub fn focus(&self) -> Option<usize> {
+        self.focus
+    }
+
+    /// Gives focus to the focusable widget at `focus` in the focus order, or
+    /// to no widget.
+    pub fn set_focus(&mut self, focus: Option<usize>) {
+        let mut position = 0;
+        for_each_focusable(self.root.as_mut(), &self.layout, &mut |widget, _| {
+            widget.set_focused(Some(position) == focus);
+            position += 1;
+        });
+
+        self.focus = focus.filter(|focus| *focus < position);
+        refresh_fingerprints(
+            self.root.as_mut(),
+            &mut self.layout,
+            &mut self.dirty_regions,
+        );
+    }
+
+    /// Moves the focus `steps` widgets along the focus order (backwards when
+    /// `steps` is negative), wrapping around at the ends.
+    pub fn step_focus(&mut self, steps: i32) {
+        let count = self.focusable_bounds().len() as i64;
+
+        if count == 0 {
+            return;
+        }
+
+        let current = match self.focus {
+            Some(focus) => focus as i64,
+            None if steps > 0 => -1,
+            None => count,
+        };
+
+        self.set_focus(Some((current + steps as i64).rem_euclid(count) as usize));
+    }
+
+    /// Moves the focus to the nearest focusable widget in `direction`. If
+    /// there is none (or no widget has focus), the focus moves along the
+    /// focus order instead: forwards for `Down` and `Right`, and backwards
+    /// for `Up` and `Left`, so that devices with only two buttons can reach
+    /// every widget.
+    pub fn move_focus(&mut self, direction: Direction) {
+        let bounds = self.focusable_bounds();
+        let nearest = self
+            .focus
+            .and_then(|focus| bounds.get(focus))
+            .and_then(|from| direction.nearest(*from, &bounds));
+
+        match (nearest, direction) {
+            (Some(nearest), _) => self.set_focus(Some(nearest)),
+            (None, Direction::Down | Direction::Right) => self.step_focus(1),
+            (None, Direction::Up | Direction::Left) => self.step_focus(-1),
+        }
+    }
+
+    /// Finds the focused widget again after an update, which may have moved
+    /// it in the focus order or made it unfocusable. Widgets that can't take
+    /// focus lose it.
+    fn refresh_focus(&mut self) {
+        let mut position = 0;
+        let mut focus = None;
+
+        for_each_widget(self.root.as_mut(), &self.layout, &mut |widget, _| {
+            let focusable = widget.focusable();
+
+            if widget.focused() {
+                if focusable && focus.is_none() {
+                    focus = Some(position);
+                } else {
+                    widget.set_focused(false);
+                }
+            }
+
+            if focusable {
+                position += 1;
+            }
+        });
+
+        self.focus = focus;
+        refresh_fingerprints(
+            self.root.as_mut(),
+            &mut self.layout,
+            &mut self.dirty_regions,
+        );
+    }
+
+    fn has_focusable(&mut self) -> bool {
+        let mut found = false;
+        for_each_focusable(self.root.as_mut(), &self.layout, &mut |_, _| found = true);
+        found
+    }
+
+    /// The bounds of the focusable widgets, in focus order.
+    fn focusable_bounds(&mut self) -> Vec<Rectangle> {
+        let mut bounds = Vec::new();
+        for_each_focusable(self.root.as_mut(), &self.layout, &mut |_, layout| {
+            bounds.push(layout.bounds)
+        });
+        bounds
+    }
+
+    /// Sends `event` to the focused widget.
+    fn focused_event(&mut self, event: &Event) -> Option<Message> {
+        let focus = self.focus?;
+        let mut position = 0;
+        let mut message = None;
+
+        for_each_focusable(self.root.as_mut(), &self.layout, &mut |widget, layout| {
+            if position == focus {
+                message = widget.event(event, layout);
+            }
+            position += 1;
+        });
+
+        message
+    }
+
+    /// Redraws the regions that changed since the last draw (the whole tree,
+    /// the first time), after filling them with `clear_color`. Returns the
+    /// regions, so that they can be flushed to the display.
+    pub fn draw(
+        &mut self,
+        display: &mut Display,
+        clear_color: Display::Color,
+    ) -> Result<DirtyRegions, Display::Error> {
+        self.root
+            .draw_regions(display, &self.layout, &self.dirty_regions, clear_color)?;
+
+        Ok(mem::take(&mut self.dirty_regions))
+    }
+}
+
+enum Update {
+    NotFound,
+    /// The widget was updated, and the layout has been recomputed.
+    Settled,
+    /// The widget was updated, but the layout of the parent of the widget
+    /// passed to `update_widget` needs to be recomputed.
+    Changed,
+}
+
+/// The parts of a widget that the layout of its parent depends on.
+#[derive(Eq, PartialEq)]
+struct Footprint {
+    height_for_width: Option<u32>,
+    intrinsic_size: IntrinsicSize,
+    layout_options: LayoutOptions,
+}
+
+impl Footprint {
+    fn new<Display>(
+        widget: &dyn Widget<Display>,
+        measurement: &Measurement,
+        layout: &LayoutNode,
+    ) -> Self
+    where
+        Display: DrawTarget,
+    {
+        Self {
+            height_for_width: widget.height_for_width(measurement, layout.bounds.size.width),
+            intrinsic_size: measurement.intrinsic_size,
+            // the key doesn't affect the layout
+            layout_options: LayoutOptions {
+                key: None,
+                ..widget.layout_options()
+            },
+        }
+    }
+}
+
+/// Calls `f` with each widget in the subtree of `widget` (including `widget`
+/// itself), in focus order.
+fn for_each_widget<Display>(
+    widget: &mut dyn Widget<Display>,
+    layout: &LayoutNode,
+    f: &mut dyn FnMut(&mut dyn Widget<Display>, &LayoutNode),
+) where
+    Display: DrawTarget,
+{
+    f(widget, layout);
+
+    for (index, child_layout) in layout.children.iter().enumerate() {
+        match widget.child_mut(index) {
+            Some(child) => for_each_widget(child, child_layout, f),
+            None => break,
+        }
+    }
+}
+
+/// Calls `f` with each focusable widget in the subtree of `widget` (including
+/// `widget` itself), in focus order.
+fn for_each_focusable<Display>(
+    widget: &mut dyn Widget<Display>,
+    layout: &LayoutNode,
+    f: &mut dyn FnMut(&mut dyn Widget<Display>, &LayoutNode),
+) where
+    Display: DrawTarget,
+{
+    for_each_widget(widget, layout, &mut |widget, layout| {
+        if widget.focusable() {
+            f(widget, layout);
+        }
+    });
+}
+
+/// Stores the current fingerprints of `widget` and its children in their
+/// layouts, and marks the widgets whose fingerprints changed as dirty.
+fn refresh_fingerprints<Display>(
+    widget: &mut dyn Widget<Display>,
+    layout: &mut LayoutNode,
+    dirty_regions: &mut DirtyRegions,
+) where
+    Display: DrawTarget,
+{
+    let fingerprint = widget.fingerprint();
+
+    if fingerprint != layout.fingerprint {
+        layout.fingerprint = fingerprint;
+        dirty_regions.add(layout.bounds);
+    }
+
+    for (index, child_layout) in layout.children.iter_mut().enumerate() {
+        match widget.child_mut(index) {
+            Some(child) => refresh_fingerprints(child, child_layout, dirty_regions),
+            None => break,
+        }
+    }
+}
+
+/// Finds the first widget with `key` in the subtree of `widget` (including
+/// `widget` itself), and appends the indices of the children leading to it to
+/// `path`.
+fn find_key<Display>(
+    widget: &mut dyn Widget<Display>,
+    layout: &LayoutNode,
+    key: Key,
+    path: &mut Vec<usize>,
+) -> bool
+where
+    Display: DrawTarget,
+{
+    if widget.layout_options().key == Some(key) {
+        return true;
+    }
+
+    for (index, child_layout) in layout.children.iter().enumerate() {
+        let child = match widget.child_mut(index) {
+            Some(child) => child,
+            None => break,
+        };
+
+        path.push(index);
+
+        if find_key(child, child_layout, key, path) {
+            return true;
+        }
+
+        path.pop();
+    }
+
+    false
+}
+
+/// Updates the widget at `path` in the subtree of `widget`, then updates the
+/// measurements and layouts on the way back up. Only the widgets on the path
+/// are measured again, using the existing measurements of their other
+/// children.
+fn update_widget<Display, W, F>(
+    widget: &mut (dyn Widget<Display> + 'static),
+    measurement: &mut Measurement,
+    layout: &mut LayoutNode,
+    path: &[usize],
+    update: F,
+    dirty_regions: &mut DirtyRegions,
+) -> Update
+where
+    Display: 'static + DrawTarget,
+    W: Widget<Display> + 'static,
+    F: FnOnce(&mut W),
+{
+    let footprint = Footprint::new(widget, measurement, layout);
+
+    match path.split_first() {
+        None => {
+            let target = match widget.as_any_mut().and_then(|any| any.downcast_mut::<W>()) {
+                Some(target) => target,
+                None => return Update::NotFound,
+            };
+
+            update(target);
+            dirty_regions.add(layout.bounds);
+            *measurement = widget.measure();
+        }
+        Some((&index, path)) => {
+            let child = match widget.child_mut(index) {
+                Some(child) => child,
+                None => return Update::NotFound,
+            };
+
+            match update_widget(
+                child,
+                &mut measurement.children[index],
+                &mut layout.children[index],
+                path,
+                update,
+                dirty_regions,
+            ) {
+                Update::Changed => {}
+                result => return result,
+            }
+
+            let children = mem::take(&mut measurement.children);
+            *measurement = widget.measure_with_children(children);
+        }
+    }
+
+    if Footprint::new(widget, measurement, layout) != footprint {
+        return Update::Changed;
+    }
+
+    // the widget keeps its bounds, so only its subtree is arranged again
+    let arranged = widget.arrange(measurement, layout.bounds);
+    arranged.collect_dirty_regions(layout, dirty_regions);
+    *layout = arranged;
+
+    Update::Settled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::{
+        container::{Axis, Container},
+        text::Text,
+        KeyCode,
+    };
+    use alloc::{rc::Rc, vec};
+    use core::{
+        any::Any,
+        cell::Cell,
+        hash::{Hash, Hasher},
+    };
+    use embedded_graphics::{
+        mock_display::MockDisplay,
+        mono_font::{ascii::FONT_6X10, MonoTextStyle},
+        pixelcolor::BinaryColor,
+    };
+
+    type Label = Text<&'static str, MonoTextStyle<'static, BinaryColor>>;
+
+    /// Switches between on and off when it's touched, and sends its new state
+    /// as a message.
+    struct Toggle(bool);
+
+    impl<Display> Widget<Display> for Toggle
+    where
+        Display: DrawTarget,
+    {
+        fn intrinsic_size(&self) -> IntrinsicSize {
+            Size::new(10, 10).into()
+        }
+
+        fn hash_appearance(&self, mut state: &mut dyn Hasher) {
+            self.0.hash(&mut state);
+        }
+
+        fn event(&mut self, event: &Event, _layout: &LayoutNode) -> Option<Message> {
+            match event {
+                Event::TouchDown(_) => {
+                    self.0 = !self.0;
+                    Some(Box::new(self.0))
+                }
+                _ => None,
+            }
+        }
+
+        fn draw_clipped(
+            &self,
+            _display: &mut Display,
+            _layout: &LayoutNode,
+            _clip: Rectangle,
+        ) -> Result<(), Display::Error> {
+            Ok(())
+        }
+    }
+
+    /// Counts how many times it is measured.
+    struct Probe {
+        layout_options: LayoutOptions,
+        measures: Rc<Cell<u32>>,
+    }
+
+    impl<Display> Widget<Display> for Probe
+    where
+        Display: 'static + DrawTarget,
+    {
+        fn intrinsic_size(&self) -> IntrinsicSize {
+            Size::new(4, 2).into()
+        }
+
+        fn layout_options(&self) -> LayoutOptions {
+            self.layout_options
+        }
+
+        fn measure(&self) -> Measurement {
+            self.measures.set(self.measures.get() + 1);
+            Measurement::new(Size::new(4, 2).into())
+        }
+
+        fn as_any_mut(&mut self) -> Option<&mut dyn Any>
+        where
+            Self: 'static,
+        {
+            Some(self)
+        }
+
+        fn draw_clipped(
+            &self,
+            _display: &mut Display,
+            _layout: &LayoutNode,
+            _clip: Rectangle,
+        ) -> Result<(), Display::Error> {
+            Ok(())
+        }
+    }
+
+    fn label(text: &'static str, key: u32) -> Box<dyn Widget<MockDisplay<BinaryColor>>> {
+        Text::new(text, MonoTextStyle::new(&FONT_6X10, BinaryColor::On))
+            .layout_options(LayoutOptions::new().key(key))
+            .boxed()
+    }
+
+    fn thermostat(first: &'static str, second: &'static str) -> Tree<MockDisplay<BinaryColor>> {
+        Tree::new(
+            Container::new()
+                .children(vec![label(first, 1), label(second, 2)])
+                .boxed(),
+            Rectangle::new(Point::zero(), Size::new(40, 20)),
+        )
+    }
+
+    #[test]
+    fn update() {
+        let mut tree = thermostat("20 C", "off");
+        tree.dirty_regions = DirtyRegions::new();
+
+        // the same size, so only the label is arranged again
+        assert!(tree.update(Key(1), |label: &mut Label| *label.text_mut() = "21 C"));
+        assert_eq!(tree.layout(), &thermostat("21 C", "off").layout);
+        assert_eq!(
+            tree.dirty_regions().rectangles(),
+            &[tree.layout().children[0].bounds]
+        );
+
+        // a different size, so the container is arranged again
+        assert!(tree.update(Key(2), |label: &mut Label| *label.text_mut() = "on"));
+        assert_eq!(tree.layout(), &thermostat("21 C", "on").layout);
+    }
+
+    #[test]
+    fn update_color() {
+        let mut tree = Tree::<MockDisplay<BinaryColor>>::new(
+            Container::new()
+                .children(vec![Container::new()
+                    .layout_options(LayoutOptions::new().key(1))
+                    .width(10)
+                    .height(10)
+                    .boxed()])
+                .boxed(),
+            Rectangle::new(Point::zero(), Size::new(40, 20)),
+        );
+        tree.dirty_regions = DirtyRegions::new();
+
+        assert!(tree.update(Key(1), |container: &mut Container<_>| {
+            container.set_background_color(BinaryColor::On)
+        }));
+        assert_eq!(
+            tree.dirty_regions().rectangles(),
+            &[tree.layout().children[0].bounds]
+        );
+    }
+
+    #[test]
+    fn update_measures_only_the_path() {
+        let measures = Rc::new(Cell::new(0));
+        let probe = |key: u32| {
+            Probe {
+                layout_options: LayoutOptions::new().key(key),
+                measures: measures.clone(),
+            }
+            .boxed()
+        };
+        let rows = (0..10)
+            .map(|row| {
+                Container::new()
+                    .axis(Axis::Horizontal)
+                    .children((0..10).map(|column| probe(row * 10 + column)).collect())
+                    .boxed()
+            })
+            .collect();
+        let mut tree = Tree::<MockDisplay<BinaryColor>>::new(
+            Container::new().children(rows).boxed(),
+            Rectangle::new(Point::zero(), Size::new(40, 20)),
+        );
+        assert_eq!(measures.get(), 100);
+
+        measures.set(0);
+        assert!(tree.update(Key(99), |_: &mut Probe| {}));
+        assert_eq!(measures.get(), 1);
+    }
+
+    #[test]
+    fn update_missing() {
+        let mut tree = thermostat("20 C", "off");
+
+        assert!(!tree.update(Key(3), |label: &mut Label| *label.text_mut() = "on"));
+        assert!(!tree.update(Key(1), |_: &mut Container<MockDisplay<BinaryColor>>| {}));
+    }
+
+    #[test]
+    fn draw() {
+        let mut tree = thermostat("20 C", "off");
+
+        let mut display = MockDisplay::new();
+        display.set_allow_overdraw(true);
+        let regions = tree.draw(&mut display, BinaryColor::Off).unwrap();
+        assert_eq!(regions.rectangles(), &[tree.bounds]);
+        assert!(tree.dirty_regions().is_empty());
+
+        tree.update(Key(1), |label: &mut Label| *label.text_mut() = "21 C");
+        let regions = tree.draw(&mut display, BinaryColor::Off).unwrap();
+        assert_eq!(regions.rectangles(), &[tree.layout().children[0].bounds]);
+    }
+
+    #[test]
+    fn event() {
+        let mut tree = Tree::<MockDisplay<BinaryColor>>::new(
+            Container::new()
+                .axis(Axis::Horizontal)
+                .children(vec![Toggle(false).boxed(), Toggle(false).boxed()])
+                .boxed(),
+            Rectangle::new(Point::zero(), Size::new(40, 20)),
+        );
+        tree.dirty_regions = DirtyRegions::new();
+
+        let message = tree.event(&Event::TouchDown(Point::new(15, 5))).unwrap();
+        assert_eq!(message.downcast_ref::<bool>(), Some(&true));
+        assert_eq!(
+            tree.dirty_regions().rectangles(),
+            &[tree.layout().children[1].bounds]
+        );
+
+        assert!(tree.event(&Event::TouchDown(Point::new(25, 5))).is_none());
+        assert!(tree.event(&Event::Encoder(1)).is_none());
+    }
+
+    #[test]
+    fn focus() {
+        let item = || {
+            Container::new()
+                .focusable(true)
+                .width(10)
+                .height(10)
+                .boxed()
+        };
+        let row = || {
+            Container::new()
+                .axis(Axis::Horizontal)
+                .children(vec![item(), item()])
+                .boxed()
+        };
+        // two rows of two items
+        let mut tree = Tree::<MockDisplay<BinaryColor>>::new(
+            Container::new().children(vec![row(), row()]).boxed(),
+            Rectangle::new(Point::zero(), Size::new(40, 40)),
+        );
+
+        tree.event(&Event::Key(KeyCode::Down));
+        assert_eq!(tree.focus(), Some(0));
+        tree.event(&Event::Key(KeyCode::Down));
+        assert_eq!(tree.focus(), Some(2));
+        tree.event(&Event::Key(KeyCode::Right));
+        assert_eq!(tree.focus(), Some(3));
+
+        // nothing further right, so the focus wraps around
+        tree.dirty_regions = DirtyRegions::new();
+        tree.event(&Event::Key(KeyCode::Right));
+        assert_eq!(tree.focus(), Some(0));
+        assert_eq!(
+            tree.dirty_regions().rectangles(),
+            &[
+                tree.layout().children[0].children[0].bounds,
+                tree.layout().children[1].children[1].bounds,
+            ]
+        );
+
+        tree.event(&Event::Encoder(-2));
+        assert_eq!(tree.focus(), Some(2));
+    }
+}